@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::iter;
+use crate::types::{
+	Type,
+	TypeScheme,
+	CompositeType
+};
+use crate::checker::TypeVariable;
+use crate::diagnostic::Span;
+
+#[derive(Clone)]
+pub struct Variable {
+	scheme: TypeScheme,
+	declaration: Span
+}
+
+impl Variable {
+	pub fn scheme(&self) -> &TypeScheme {
+		&self.scheme
+	}
+
+	pub fn declaration(&self) -> Span {
+		self.declaration
+	}
+}
+
+// Structs are named apart from variables, so a struct and a variable can share a name.
+#[derive(Clone, Default)]
+struct Scope {
+	variables: HashMap<String, Variable>,
+	structs: HashMap<String, Type>
+}
+
+// The types of the variables and structs in scope, laid out in scopes and frames like the runtime `Environment`.
+// It also holds what has been inferred about every type variable, which the types of variables may refer to.
+#[derive(Clone)]
+pub struct TypeEnvironment {
+	scopes: Vec<Scope>,
+	frames: Vec<usize>,
+	type_variables: Vec<TypeVariable>
+}
+
+impl TypeEnvironment {
+	pub fn new() -> Self {
+		Self {
+			scopes: vec![Scope::default()],
+			frames: Vec::new(),
+			type_variables: Vec::new()
+		}
+	}
+
+	pub fn push_scope(&mut self) {
+		self.scopes.push(Scope::default());
+	}
+
+	pub fn pop_scope(&mut self) {
+		self.scopes.pop();
+	}
+
+	pub fn push_frame(&mut self) {
+		self.frames.push(self.scopes.len());
+		self.push_scope();
+	}
+
+	pub fn pop_frame(&mut self) {
+		if let Some(start) = self.frames.pop() {
+			self.scopes.truncate(start);
+		}
+	}
+
+	// The index of the innermost scope, for `redefine`.
+	pub fn current_scope(&self) -> usize {
+		self.scopes.len() - 1
+	}
+
+	pub fn define(&mut self, name: &str, scheme: TypeScheme, declaration: Span) {
+		if let Some(scope) = self.scopes.last_mut() {
+			scope.variables.insert(name.to_string(), Variable {
+				scheme,
+				declaration
+			});
+		}
+	}
+
+	// Replaces the scheme of the variable declared at `declaration` in the given scope, unless it has been shadowed since.
+	pub fn redefine(&mut self, scope: usize, name: &str, scheme: TypeScheme, declaration: Span) {
+		if let Some(variable) = self.scopes.get_mut(scope).and_then(|scope| scope.variables.get_mut(name)) {
+			if variable.declaration == declaration {
+				variable.scheme = scheme;
+			}
+		}
+	}
+
+	pub fn get(&self, name: &str) -> Option<&Variable> {
+		self.visible_scopes().find_map(|scope| scope.variables.get(name))
+	}
+
+	pub fn define_struct(&mut self, name: &str, struct_type: Type) {
+		if let Some(scope) = self.scopes.last_mut() {
+			scope.structs.insert(name.to_string(), struct_type);
+		}
+	}
+
+	pub fn get_struct(&self, name: &str) -> Option<&Type> {
+		self.visible_scopes().find_map(|scope| scope.structs.get(name))
+	}
+
+	// The visible structs with a field of the given name, innermost first and without those shadowed by them.
+	pub fn structs_with_field(&self, field: &str) -> Vec<&Type> {
+		let mut names = Vec::new();
+		let mut structs = Vec::new();
+
+		for (name, struct_type) in self.visible_scopes().flat_map(|scope| &scope.structs) {
+			if names.contains(&name) {
+				continue;
+			}
+
+			names.push(name);
+
+			if let Type::Composite(CompositeType::UserDefined { fields, .. }) = struct_type {
+				if fields.iter().any(|(name, _)| name == field) {
+					structs.push(struct_type);
+				}
+			}
+		}
+
+		structs
+	}
+
+	// The scopes visible from the current frame, innermost first.
+	fn visible_scopes(&self) -> impl Iterator<Item = &Scope> {
+		let start = self.frames.last().copied().unwrap_or(1);
+
+		(start..self.scopes.len()).rev()
+			.chain(iter::once(0))
+			.map(|index| &self.scopes[index])
+	}
+
+	pub fn add_type_variable(&mut self, type_variable: TypeVariable) -> usize {
+		self.type_variables.push(type_variable);
+		self.type_variables.len() - 1
+	}
+
+	pub fn type_variable(&self, index: usize) -> &TypeVariable {
+		&self.type_variables[index]
+	}
+
+	pub fn type_variable_mut(&mut self, index: usize) -> &mut TypeVariable {
+		&mut self.type_variables[index]
+	}
+
+	pub fn type_variables(&self) -> &[TypeVariable] {
+		&self.type_variables
+	}
+
+	pub fn restore_type_variables(&mut self, type_variables: Vec<TypeVariable>) {
+		self.type_variables = type_variables;
+	}
+}
@@ -0,0 +1,571 @@
+use crate::syntax::{
+	Expression,
+	BinaryExpression,
+	BinaryExpressionKind,
+	UnaryExpression,
+	UnaryExpressionKind,
+	LiteralExpression,
+	LiteralExpressionKind,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	StringPart,
+	InterpolatedStringExpression
+};
+use crate::types::{
+	Type,
+	PrimitiveType,
+	CompositeType
+};
+use crate::diagnostic::{
+	Diagnostic,
+	Span,
+	pluralise,
+	quote_list
+};
+use crate::checker::Checker;
+use crate::checker::environment::Variable;
+use crate::checker::inference::{
+	Constraint,
+	Origin
+};
+
+impl Checker<'_> {
+	pub(super) fn check_expression(&self, expression: &Expression) -> Type {
+		match expression {
+			Expression::Binary(binary_expression) => self.check_binary_expression(binary_expression),
+			Expression::Unary(unary_expression) => self.check_unary_expression(unary_expression),
+			Expression::Literal(literal_expression) => check_literal_expression(literal_expression),
+			Expression::Parenthesised(parenthesised_expression) => self.check_expression(parenthesised_expression.content()),
+			Expression::Identifier(identifier_expression) => self.check_identifier_expression(identifier_expression),
+			Expression::Assignment(assignment_expression) => self.check_assignment_expression(assignment_expression),
+			Expression::Call(call_expression) => self.check_call_expression(call_expression),
+			Expression::StructLiteral(struct_literal_expression) => self.check_struct_literal_expression(struct_literal_expression),
+			Expression::FieldAccess(field_access_expression) => self.check_field_access_expression(field_access_expression, false),
+			Expression::ArrayLiteral(array_literal_expression) => self.check_array_literal_expression(array_literal_expression),
+			Expression::Index(index_expression) => self.check_index_expression(index_expression),
+			Expression::InterpolatedString(interpolated_string_expression) => self.check_interpolated_string_expression(interpolated_string_expression),
+			// Already reported by the parser.
+			Expression::Error(_) => Type::Unknown
+		}
+	}
+
+	// Checks an expression that must be of the `expected` type.
+	pub(super) fn check_expression_against(&self, expression: &Expression, expected: &Type) {
+		let found = self.check_expression(expression);
+		self.expect_type(&found, expected, expression.span());
+	}
+
+	fn check_binary_expression(&self, binary_expression: &BinaryExpression) -> Type {
+		let kind = binary_expression.kind();
+		let boolean = Type::Primitive(PrimitiveType::Boolean);
+
+		if matches!(kind, BinaryExpressionKind::LogicalAnd | BinaryExpressionKind::LogicalOr) {
+			self.check_expression_against(binary_expression.left_operand(), &boolean);
+			self.check_expression_against(binary_expression.right_operand(), &boolean);
+
+			return boolean;
+		}
+
+		let left_operand = self.check_expression(binary_expression.left_operand());
+		let right_operand = self.check_expression(binary_expression.right_operand());
+
+		let result_type = match (self.resolve(&left_operand), self.resolve(&right_operand)) {
+			(left @ (Type::Primitive(_) | Type::Unknown), right @ (Type::Primitive(_) | Type::Unknown)) => binary_result_type(kind, &left, &right),
+			_ => self.infer_binary_result_type(binary_expression, &left_operand, &right_operand)
+		};
+
+		match result_type {
+			Some(result_type) => result_type,
+			None => {
+				let left_operand = self.resolve_fully(&left_operand);
+				let right_operand = self.resolve_fully(&right_operand);
+
+				self.report(
+					Diagnostic::error(
+						"E0302",
+						format!("cannot apply `{}` to `{}` and `{}`", kind.symbol(), left_operand, right_operand),
+						binary_expression.operator().span()
+					)
+						.with_label(binary_expression.left_operand().span(), format!("this is of type `{}`", left_operand))
+						.with_label(binary_expression.right_operand().span(), format!("this is of type `{}`", right_operand))
+				);
+
+				Type::Unknown
+			}
+		}
+	}
+
+	// While an operand's type is still being inferred both operands must be of the same type,
+	// as an integer can not become a float once it is known to be one.
+	fn infer_binary_result_type(&self, binary_expression: &BinaryExpression, left_operand: &Type, right_operand: &Type) -> Option<Type> {
+		let origin = Origin::Inference(binary_expression.span());
+		let operator = binary_expression.operator().span();
+
+		let (constraint, result_type) = match binary_expression.kind() {
+			BinaryExpressionKind::Range => {
+				let integer = Type::Primitive(PrimitiveType::Integer);
+				let valid = self.try_unify(left_operand, &integer, origin) && self.try_unify(right_operand, &integer, origin);
+
+				return valid.then_some(Type::Primitive(PrimitiveType::Range));
+			},
+			BinaryExpressionKind::Equality |
+			BinaryExpressionKind::NotEquality => (Constraint::Any, Type::Primitive(PrimitiveType::Boolean)),
+			BinaryExpressionKind::LessThan |
+			BinaryExpressionKind::GreaterThan |
+			BinaryExpressionKind::LessThanEqualTo |
+			BinaryExpressionKind::GreaterThanEqualTo => (Constraint::Ordered, Type::Primitive(PrimitiveType::Boolean)),
+			BinaryExpressionKind::Addition => (Constraint::Addable, left_operand.clone()),
+			_ => (Constraint::Number, left_operand.clone())
+		};
+
+		let valid = self.try_unify(left_operand, right_operand, origin) && self.constrain(left_operand, constraint, operator).is_ok();
+
+		valid.then_some(result_type)
+	}
+
+	fn check_unary_expression(&self, unary_expression: &UnaryExpression) -> Type {
+		let operand = self.check_expression(unary_expression.operand());
+		let operator = unary_expression.operator().span();
+
+		let valid = match unary_expression.kind() {
+			UnaryExpressionKind::Identity |
+			UnaryExpressionKind::Negation => self.constrain(&operand, Constraint::Number, operator).is_ok(),
+			UnaryExpressionKind::LogicalNot => self.try_unify(&operand, &Type::Primitive(PrimitiveType::Boolean), Origin::Inference(unary_expression.span()))
+		};
+
+		if valid {
+			return operand;
+		}
+
+		let operand = self.resolve_fully(&operand);
+
+		self.report(
+			Diagnostic::error("E0303", format!("cannot apply unary `{}` to `{}`", unary_expression.kind().symbol(), operand), operator)
+				.with_label(unary_expression.operand().span(), format!("this is of type `{}`", operand))
+		);
+
+		Type::Unknown
+	}
+
+	// Generic functions are instantiated afresh for every use.
+	fn check_identifier_expression(&self, identifier_expression: &IdentifierExpression) -> Type {
+		match self.lookup(identifier_expression.name()) {
+			Some(variable) => self.instantiate(variable.scheme()),
+			None => {
+				self.report_undefined_variable(identifier_expression);
+				Type::Unknown
+			}
+		}
+	}
+
+	// Looks a variable up, first checking the body of a function that has not been yet so its type is known.
+	fn lookup(&self, name: &str) -> Option<Variable> {
+		let variable = self.environment.borrow().get(name).cloned()?;
+		let pending = self.pending_functions.borrow().iter()
+			.position(|function| function.declaration.signature() == variable.declaration() && !function.checking);
+
+		match pending {
+			Some(index) => {
+				self.check_pending_function(index);
+				self.environment.borrow().get(name).cloned()
+			},
+			None => Some(variable)
+		}
+	}
+
+	fn report_undefined_variable(&self, identifier_expression: &IdentifierExpression) {
+		self.report(
+			Diagnostic::error("E0301", format!("cannot find variable `{}`", identifier_expression.name()), identifier_expression.span())
+				.with_primary_label("not declared in this scope")
+				.with_note(format!("declare it first with `let {} = ...`", identifier_expression.name()))
+		);
+	}
+
+	// Variables and fields keep the type they were declared with. A generic variable can not be assigned to,
+	// as nothing makes sure the new value is as generic as the old one.
+	fn check_assignment_expression(&self, assignment_expression: &AssignmentExpression) -> Type {
+		let value = self.check_expression(assignment_expression.value());
+
+		if let Expression::Identifier(identifier_expression) = assignment_expression.target() {
+			let generic = self.lookup(identifier_expression.name()).filter(|variable| !variable.scheme().variables().is_empty());
+
+			if let Some(variable) = generic {
+				self.report(
+					Diagnostic::error("E0318", format!("cannot assign to `{}`, its type is generic", identifier_expression.name()), identifier_expression.span())
+						.with_primary_label("cannot be assigned to")
+						.with_label(variable.declaration(), "declared here with a generic type")
+						.with_note("declare a new variable for the other value instead")
+				);
+
+				return value;
+			}
+		}
+
+		let target = self.check_expression(assignment_expression.target());
+		self.expect_type(&value, &target, assignment_expression.value().span());
+
+		value
+	}
+
+	fn check_call_expression(&self, call_expression: &CallExpression) -> Type {
+		let callee = match call_expression.callee() {
+			Expression::FieldAccess(field_access_expression) => self.check_field_access_expression(field_access_expression, true),
+			callee => self.check_expression(callee)
+		};
+		let arguments = call_expression.arguments().iter().map(|argument| self.check_expression(argument)).collect::<Vec<_>>();
+
+		let (parameters, result) = match self.resolve(&callee) {
+			Type::Composite(CompositeType::Function { parameters, result }) => (parameters, *result),
+			// Calling something whose type is still being inferred makes it a function.
+			Type::Variable(_) => {
+				let parameters = arguments.iter().map(|_| self.fresh_variable()).collect::<Vec<_>>();
+				let result = self.fresh_variable();
+
+				if !self.expect_type(&Type::function(parameters.clone(), result.clone()), &callee, call_expression.callee().span()) {
+					return Type::Unknown;
+				}
+
+				(parameters, result)
+			},
+			Type::Unknown => return Type::Unknown,
+			callee => {
+				let callee = self.resolve_fully(&callee);
+
+				self.report(
+					Diagnostic::error("E0305", format!("cannot call `{}`", callee), call_expression.callee().span())
+						.with_primary_label(format!("this is of type `{}`", callee))
+						.with_note("only functions can be called")
+				);
+
+				return Type::Unknown;
+			}
+		};
+
+		if parameters.len() != arguments.len() {
+			let mut diagnostic = Diagnostic::error(
+				"E0306",
+				format!("this function takes {} but {} {} given", pluralise(parameters.len(), "argument"), arguments.len(), if arguments.len() == 1 { "was" } else { "were" }),
+				call_expression.span()
+			)
+				.with_primary_label(format!("called with {}", pluralise(arguments.len(), "argument")));
+
+			// Point at the declaration when the function is called by name.
+			if let Expression::Identifier(identifier_expression) = call_expression.callee() {
+				if let Some(variable) = self.environment.borrow().get(identifier_expression.name()) {
+					diagnostic = diagnostic.with_label(variable.declaration(), format!("defined here with {}", pluralise(parameters.len(), "parameter")));
+				}
+			}
+
+			self.report(diagnostic);
+		} else {
+			for ((argument, argument_type), parameter) in call_expression.arguments().iter().zip(&arguments).zip(&parameters) {
+				self.expect_type(argument_type, parameter, argument.span());
+			}
+		}
+
+		result
+	}
+
+	// Every field must be given exactly once, in any order.
+	fn check_struct_literal_expression(&self, struct_literal_expression: &StructLiteralExpression) -> Type {
+		let struct_type = self.environment.borrow().get_struct(struct_literal_expression.name()).cloned();
+
+		let Some(Type::Composite(CompositeType::UserDefined { name, fields })) = &struct_type else {
+			self.report_unknown_type(struct_literal_expression.name(), struct_literal_expression.name_span());
+
+			for field in struct_literal_expression.fields() {
+				self.check_expression(field.value());
+			}
+
+			return Type::Unknown;
+		};
+
+		let mut given: Vec<&str> = Vec::new();
+
+		for field in struct_literal_expression.fields() {
+			let value = self.check_expression(field.value());
+
+			if given.contains(&field.name()) {
+				let first = struct_literal_expression.fields().iter().find(|first| first.name() == field.name()).unwrap_or(field);
+
+				self.report(
+					Diagnostic::error("E0314", format!("field `{}` is given more than once", field.name()), field.span())
+						.with_primary_label("given again here")
+						.with_label(first.span(), "first given here")
+				);
+
+				continue;
+			}
+
+			given.push(field.name());
+
+			match fields.iter().find(|(declared, _)| declared == field.name()) {
+				Some((_, field_type)) => {
+					self.expect_type(&value, field_type, field.value().span());
+				},
+				None => self.report_unknown_field(name, fields, field.name(), field.span())
+			}
+		}
+
+		let missing = fields.iter()
+			.map(|(declared, _)| declared.as_str())
+			.filter(|declared| !given.contains(declared))
+			.collect::<Vec<_>>();
+
+		if !missing.is_empty() {
+			self.report(
+				Diagnostic::error(
+					"E0313",
+					format!("missing {} {} in `{}`", if missing.len() == 1 { "field" } else { "fields" }, quote_list(&missing), name),
+					struct_literal_expression.span()
+				)
+					.with_primary_label(format!("{} not given", pluralise(missing.len(), "field")))
+			);
+		}
+
+		struct_type.unwrap_or(Type::Unknown)
+	}
+
+	// Accessing a field on something whose type is still being inferred makes it the one struct with that field.
+	// `called` tells whether the field is called right away, as the methods of arrays must be.
+	fn check_field_access_expression(&self, field_access_expression: &FieldAccessExpression, called: bool) -> Type {
+		let object = self.check_expression(field_access_expression.object());
+		let field = field_access_expression.field();
+
+		if let Some(method) = self.check_array_method(field_access_expression, &object, called) {
+			return method;
+		}
+
+		let struct_type = match self.resolve(&object) {
+			Type::Variable(_) => {
+				let candidates = self.environment.borrow().structs_with_field(field).into_iter().cloned().collect::<Vec<_>>();
+
+				match candidates.as_slice() {
+					[struct_type] => {
+						self.expect_type(struct_type, &object, field_access_expression.object().span());
+						struct_type.clone()
+					},
+					[] => {
+						self.report(
+							Diagnostic::error("E0312", format!("no struct has a field `{}`", field), field_access_expression.field_span())
+								.with_primary_label("unknown field")
+						);
+
+						return Type::Unknown;
+					},
+					candidates => {
+						// Scopes keep their structs unordered, the names are sorted so the note reads the same every time.
+						let mut names = candidates.iter().map(|candidate| candidate.to_string()).collect::<Vec<_>>();
+						names.sort();
+						let quantifier = if names.len() == 2 { "both" } else { "all" };
+
+						self.report(
+							Diagnostic::error("E0315", "type annotations needed", field_access_expression.object().span())
+								.with_primary_label("cannot tell which struct this is")
+								.with_note(format!("{} {} have a field `{}`, annotate the type of this", quote_list(&names), quantifier, field))
+						);
+
+						return Type::Unknown;
+					}
+				}
+			},
+			struct_type => struct_type
+		};
+
+		match &struct_type {
+			Type::Composite(CompositeType::UserDefined { name, fields }) => match fields.iter().find(|(declared, _)| declared == field) {
+				Some((_, field_type)) => field_type.clone(),
+				None => {
+					self.report_unknown_field(name, fields, field, field_access_expression.field_span());
+					Type::Unknown
+				}
+			},
+			Type::Unknown => Type::Unknown,
+			found => {
+				let found = self.resolve_fully(found);
+
+				self.report(
+					Diagnostic::error("E0312", format!("no field `{}` on type `{}`", field, found), field_access_expression.field_span())
+						.with_primary_label("unknown field")
+						.with_label(field_access_expression.object().span(), format!("this is of type `{}`", found))
+						.with_note("only structs have fields")
+				);
+
+				Type::Unknown
+			}
+		}
+	}
+
+	// The type of `len`, `push` or `pop` on an array. Calling one on something whose type is still being inferred
+	// makes it an array, unless a struct has a field of that name.
+	fn check_array_method(&self, field_access_expression: &FieldAccessExpression, object: &Type, called: bool) -> Option<Type> {
+		let field = field_access_expression.field();
+
+		if !ARRAY_METHODS.contains(&field) {
+			return None;
+		}
+
+		let element = match self.resolve(object) {
+			Type::Composite(CompositeType::Array(element)) => *element,
+			Type::Variable(_) if called && self.environment.borrow().structs_with_field(field).is_empty() => {
+				self.indexed_element_type(object, field_access_expression.object().span())
+			},
+			_ => return None
+		};
+
+		if !called {
+			let object = self.resolve_fully(object);
+
+			self.report(
+				Diagnostic::error("E0312", format!("no field `{}` on type `{}`", field, object), field_access_expression.field_span())
+					.with_primary_label("unknown field")
+					.with_note(format!("`{}` is a method of arrays, call it as `.{}()`", field, field))
+			);
+
+			return Some(Type::Unknown);
+		}
+
+		Some(match field {
+			"len" => Type::function(Vec::new(), Type::Primitive(PrimitiveType::Integer)),
+			"push" => Type::function(vec![element], Type::Primitive(PrimitiveType::Unit)),
+			_ => Type::function(Vec::new(), element)
+		})
+	}
+
+	// Every element must be of the type of the first one.
+	fn check_array_literal_expression(&self, array_literal_expression: &ArrayLiteralExpression) -> Type {
+		let element = self.fresh_variable();
+
+		for element_expression in array_literal_expression.elements() {
+			self.check_expression_against(element_expression, &element);
+		}
+
+		Type::array(element)
+	}
+
+	// Indexing with an `Integer` gives an element, indexing with a `Range` a slice of the array.
+	fn check_index_expression(&self, index_expression: &IndexExpression) -> Type {
+		let object = self.check_expression(index_expression.object());
+		let index = self.check_expression(index_expression.index());
+		let element = self.indexed_element_type(&object, index_expression.object().span());
+
+		if self.resolve(&index) == Type::Primitive(PrimitiveType::Range) {
+			return Type::array(element);
+		}
+
+		self.expect_type(&index, &Type::Primitive(PrimitiveType::Integer), index_expression.index().span());
+
+		element
+	}
+
+	// The type of the elements of an array, something whose type is still being inferred becomes an array.
+	fn indexed_element_type(&self, indexed: &Type, span: Span) -> Type {
+		match self.resolve(indexed) {
+			Type::Composite(CompositeType::Array(element)) => *element,
+			Type::Variable(_) => {
+				let element = self.fresh_variable();
+
+				if self.expect_type(&Type::array(element.clone()), indexed, span) {
+					element
+				} else {
+					Type::Unknown
+				}
+			},
+			Type::Unknown => Type::Unknown,
+			found => {
+				let found = self.resolve_fully(&found);
+
+				self.report(
+					Diagnostic::error("E0316", format!("cannot index into `{}`", found), span)
+						.with_primary_label(format!("this is of type `{}`", found))
+						.with_note("only arrays can be indexed")
+				);
+
+				Type::Unknown
+			}
+		}
+	}
+
+	// Values of every type can be formatted into a string.
+	fn check_interpolated_string_expression(&self, interpolated_string_expression: &InterpolatedStringExpression) -> Type {
+		for part in interpolated_string_expression.parts() {
+			if let StringPart::Expression(expression) = part {
+				self.check_expression(expression);
+			}
+		}
+
+		Type::Primitive(PrimitiveType::String)
+	}
+
+	fn report_unknown_field(&self, name: &str, fields: &[(String, Type)], field: &str, span: Span) {
+		let names = fields.iter().map(|(declared, _)| declared.as_str()).collect::<Vec<_>>();
+		let mut diagnostic = Diagnostic::error("E0312", format!("no field `{}` on type `{}`", field, name), span)
+			.with_primary_label("unknown field");
+
+		if !names.is_empty() {
+			diagnostic = diagnostic.with_note(format!("the fields of `{}` are {}", name, quote_list(&names)));
+		}
+
+		self.report(diagnostic);
+	}
+}
+
+// The methods of arrays, they are looked up like fields but can only be called.
+const ARRAY_METHODS: [&str; 3] = ["len", "push", "pop"];
+
+// The type of a binary operation on operands of the given types, if they can be combined at all.
+// Integers mix with floats as they do at runtime, and nothing can be said against unknown operands.
+fn binary_result_type(kind: BinaryExpressionKind, left_operand: &Type, right_operand: &Type) -> Option<Type> {
+	let integer = Type::Primitive(PrimitiveType::Integer);
+	let boolean = Type::Primitive(PrimitiveType::Boolean);
+	let ordered = |operand: &Type| operand.is_numeric() || matches!(operand, Type::Primitive(PrimitiveType::Character | PrimitiveType::String));
+
+	if *left_operand == Type::Unknown || *right_operand == Type::Unknown {
+		return Some(match kind {
+			BinaryExpressionKind::Range => Type::Primitive(PrimitiveType::Range),
+			BinaryExpressionKind::Addition |
+			BinaryExpressionKind::Substraction |
+			BinaryExpressionKind::Multiplication |
+			BinaryExpressionKind::Division |
+			BinaryExpressionKind::Modulo => Type::Unknown,
+			_ => boolean
+		});
+	}
+
+	let both_numeric = left_operand.is_numeric() && right_operand.is_numeric();
+
+	match kind {
+		BinaryExpressionKind::Equality |
+		BinaryExpressionKind::NotEquality => (left_operand == right_operand || both_numeric).then_some(boolean),
+
+		BinaryExpressionKind::LessThan |
+		BinaryExpressionKind::GreaterThan |
+		BinaryExpressionKind::LessThanEqualTo |
+		BinaryExpressionKind::GreaterThanEqualTo => (both_numeric || (left_operand == right_operand && ordered(left_operand))).then_some(boolean),
+
+		BinaryExpressionKind::Range => (*left_operand == integer && *right_operand == integer).then_some(Type::Primitive(PrimitiveType::Range)),
+
+		BinaryExpressionKind::Addition if *left_operand == Type::Primitive(PrimitiveType::String) && left_operand == right_operand => {
+			Some(Type::Primitive(PrimitiveType::String))
+		},
+
+		_ if *left_operand == integer && *right_operand == integer => Some(integer),
+		_ if both_numeric => Some(Type::Primitive(PrimitiveType::Float)),
+		_ => None
+	}
+}
+
+fn check_literal_expression(literal_expression: &LiteralExpression) -> Type {
+	Type::Primitive(match literal_expression.kind() {
+		LiteralExpressionKind::Integer => PrimitiveType::Integer,
+		LiteralExpressionKind::FloatingPoint => PrimitiveType::Float,
+		LiteralExpressionKind::String => PrimitiveType::String,
+		LiteralExpressionKind::Character => PrimitiveType::Character,
+		LiteralExpressionKind::Boolean => PrimitiveType::Boolean
+	})
+}
@@ -0,0 +1,511 @@
+use std::rc::Rc;
+use crate::syntax::TypeAnnotation;
+use crate::types::{
+	Type,
+	TypeScheme,
+	PrimitiveType,
+	CompositeType
+};
+use crate::diagnostic::{
+	Diagnostic,
+	Span
+};
+use crate::checker::Checker;
+
+// The types a type variable may still stand for, narrowed by the operators applied to it.
+// Each allows fewer types than the ones after it, so the narrower of two is their intersection.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Constraint {
+	Number,
+	Addable,
+	Ordered,
+	Any
+}
+
+impl Constraint {
+	fn allows(self, allowed_type: &Type) -> bool {
+		match allowed_type {
+			Type::Primitive(primitive_type) => match self {
+				Constraint::Number => matches!(primitive_type, PrimitiveType::Integer | PrimitiveType::Float),
+				Constraint::Addable => matches!(primitive_type, PrimitiveType::Integer | PrimitiveType::Float | PrimitiveType::String),
+				Constraint::Ordered => matches!(primitive_type, PrimitiveType::Integer | PrimitiveType::Float | PrimitiveType::String | PrimitiveType::Character),
+				Constraint::Any => true
+			},
+			Type::Unknown => true,
+			_ => self == Constraint::Any
+		}
+	}
+
+	fn description(self) -> &'static str {
+		match self {
+			Constraint::Number => "a number",
+			Constraint::Addable => "a number or `String`",
+			Constraint::Ordered => "a number, `Character` or `String`",
+			Constraint::Any => "any type"
+		}
+	}
+}
+
+// Why a type variable was bound, to explain conflicts with it.
+#[derive(Clone, Copy)]
+pub enum Origin {
+	Annotation(Span),
+	Inference(Span)
+}
+
+impl Origin {
+	fn span(self) -> Span {
+		match self {
+			Origin::Annotation(span) |
+			Origin::Inference(span) => span
+		}
+	}
+
+	fn label(self, bound_type: &Type) -> String {
+		match self {
+			Origin::Annotation(_) => format!("`{}` because of this annotation", bound_type),
+			Origin::Inference(_) => format!("inferred to be `{}` here", bound_type)
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct TypeVariable {
+	binding: Option<Type>,
+	// How many function bodies deep the variable was made, only those deeper than a function are generalised with it.
+	level: usize,
+	constraint: Constraint,
+	origin: Option<Origin>,
+	// The operator that narrowed the constraint last.
+	constraint_origin: Option<Span>
+}
+
+// Why two types could not be unified.
+pub enum Conflict {
+	Mismatch,
+	Constraint {
+		constraint: Constraint,
+		origin: Option<Span>,
+		found: Type
+	},
+	// A type variable would have to contain itself, as in `fn f(x) { x(x) }`.
+	Infinite
+}
+
+impl Checker<'_> {
+	pub(super) fn fresh_variable(&self) -> Type {
+		let index = self.environment.borrow_mut().add_type_variable(TypeVariable {
+			binding: None,
+			level: self.level.get(),
+			constraint: Constraint::Any,
+			origin: None,
+			constraint_origin: None
+		});
+
+		Type::Variable(index)
+	}
+
+	// A new variable bound to `bound_type`, so conflicts with it can point at where that type came from.
+	pub(super) fn variable_bound_to(&self, bound_type: Type, origin: Origin) -> Type {
+		let variable = self.fresh_variable();
+
+		if let Type::Variable(index) = variable {
+			let mut environment = self.environment.borrow_mut();
+			let type_variable = environment.type_variable_mut(index);
+			type_variable.binding = Some(bound_type);
+			type_variable.origin = Some(origin);
+		}
+
+		variable
+	}
+
+	// Without an annotation the type is left to be inferred, with one that does not name a type nothing is known about it.
+	pub(super) fn annotated_type(&self, annotation: Option<&TypeAnnotation>) -> Type {
+		let Some(annotation) = annotation else {
+			return self.fresh_variable();
+		};
+
+		match self.annotation_type(annotation) {
+			Some(annotated_type) => self.variable_bound_to(annotated_type, Origin::Annotation(annotation.span())),
+			None => Type::Unknown
+		}
+	}
+
+	// The type an annotation stands for, reporting the names in it that are not types.
+	fn annotation_type(&self, annotation: &TypeAnnotation) -> Option<Type> {
+		match annotation {
+			TypeAnnotation::Named(name) => {
+				if let Some(primitive_type) = PrimitiveType::from_name(name.text()) {
+					return Some(Type::Primitive(primitive_type));
+				}
+
+				let pending = self.pending_structs.borrow().iter()
+					.find(|pending| pending.declaration.name() == name.text())
+					.map(|pending| (Rc::clone(&pending.declaration), pending.resolving));
+
+				match pending {
+					Some((_, true)) => {
+						self.report_recursive_struct(name.text(), name.span());
+						return None;
+					},
+					Some((struct_declaration, false)) => self.declare_struct(&struct_declaration),
+					None => ()
+				}
+
+				let named_type = self.environment.borrow().get_struct(name.text()).cloned();
+
+				if named_type.is_none() {
+					self.report_unknown_type(name.text(), name.span());
+				}
+
+				named_type
+			},
+			TypeAnnotation::Array { element, .. } => self.annotation_type(element).map(Type::array)
+		}
+	}
+
+	pub(super) fn report_unknown_type(&self, name: &str, span: Span) {
+		self.report(
+			Diagnostic::error("E0300", format!("cannot find type `{}`", name), span)
+				.with_primary_label("not a known type")
+				.with_note("the types are `Integer`, `Float`, `String`, `Character`, `Boolean`, `Range`, the declared structs and arrays of them such as `[Integer]`")
+		);
+	}
+
+	fn report_recursive_struct(&self, name: &str, span: Span) {
+		self.report(
+			Diagnostic::error("E0319", format!("struct `{}` contains itself", name), span)
+				.with_primary_label(format!("a `{}` would have to hold another `{}` here", name, name))
+				.with_note("a field cannot hold the struct it belongs to, not even through another struct or an array")
+		);
+	}
+
+	// Follows bound type variables until a type that is not one, or one that is still unbound.
+	pub(super) fn resolve(&self, resolved_type: &Type) -> Type {
+		let environment = self.environment.borrow();
+		let mut resolved_type = resolved_type.clone();
+
+		while let Type::Variable(index) = resolved_type {
+			match &environment.type_variable(index).binding {
+				Some(binding) => resolved_type = binding.clone(),
+				None => break
+			}
+		}
+
+		resolved_type
+	}
+
+	// Resolves the type variables inside arrays and function types as well, to show the type in a diagnostic.
+	pub(super) fn resolve_fully(&self, resolved_type: &Type) -> Type {
+		match self.resolve(resolved_type) {
+			Type::Composite(CompositeType::Array(element)) => Type::array(self.resolve_fully(&element)),
+			Type::Composite(CompositeType::Function { parameters, result }) => Type::function(
+				parameters.iter().map(|parameter| self.resolve_fully(parameter)).collect(),
+				self.resolve_fully(&result)
+			),
+			resolved_type => resolved_type
+		}
+	}
+
+	// Where the type a variable resolves to was first bound.
+	fn origin(&self, origin_type: &Type) -> Option<Origin> {
+		let environment = self.environment.borrow();
+		let mut origin_type = origin_type;
+		let mut origin = None;
+
+		while let Type::Variable(index) = origin_type {
+			let type_variable = environment.type_variable(*index);
+
+			match &type_variable.binding {
+				Some(binding) => {
+					origin = type_variable.origin.or(origin);
+					origin_type = binding;
+				},
+				None => break
+			}
+		}
+
+		origin
+	}
+
+	pub(super) fn unify(&self, left: &Type, right: &Type, origin: Origin) -> Result<(), Conflict> {
+		match (self.resolve(left), self.resolve(right)) {
+			(Type::Unknown, _) |
+			(_, Type::Unknown) => Ok(()),
+			// Structs unify this way only, each is the same type as nothing but itself.
+			(left, right) if left == right => Ok(()),
+			(Type::Variable(variable), other) |
+			(other, Type::Variable(variable)) => self.bind(variable, other, origin),
+			(Type::Composite(CompositeType::Array(left_element)), Type::Composite(CompositeType::Array(right_element))) => {
+				self.unify(&left_element, &right_element, origin)
+			},
+			(
+				Type::Composite(CompositeType::Function { parameters: left_parameters, result: left_result }),
+				Type::Composite(CompositeType::Function { parameters: right_parameters, result: right_result })
+			) if left_parameters.len() == right_parameters.len() => {
+				for (left_parameter, right_parameter) in left_parameters.iter().zip(&right_parameters) {
+					self.unify(left_parameter, right_parameter, origin)?;
+				}
+
+				self.unify(&left_result, &right_result, origin)
+			},
+			_ => Err(Conflict::Mismatch)
+		}
+	}
+
+	// Like `unify`, but leaves every type variable as it was when the types do not unify.
+	pub(super) fn try_unify(&self, left: &Type, right: &Type, origin: Origin) -> bool {
+		let type_variables = self.environment.borrow().type_variables().to_vec();
+
+		if self.unify(left, right, origin).is_ok() {
+			true
+		} else {
+			self.environment.borrow_mut().restore_type_variables(type_variables);
+			false
+		}
+	}
+
+	fn bind(&self, index: usize, bound_type: Type, origin: Origin) -> Result<(), Conflict> {
+		let (level, constraint, constraint_origin) = {
+			let environment = self.environment.borrow();
+			let type_variable = environment.type_variable(index);
+			(type_variable.level, type_variable.constraint, type_variable.constraint_origin)
+		};
+
+		if self.occurs(index, level, &bound_type) {
+			return Err(Conflict::Infinite);
+		}
+
+		if let Type::Variable(other) = bound_type {
+			// The variable bound to keeps the narrower constraint of the two.
+			let mut environment = self.environment.borrow_mut();
+			let other = environment.type_variable_mut(other);
+
+			if constraint < other.constraint {
+				other.constraint = constraint;
+				other.constraint_origin = constraint_origin;
+			}
+		} else if !constraint.allows(&bound_type) {
+			// Nothing more is reported about the variable once it is wrong.
+			self.environment.borrow_mut().type_variable_mut(index).binding = Some(Type::Unknown);
+
+			return Err(Conflict::Constraint {
+				constraint,
+				origin: constraint_origin,
+				found: bound_type
+			});
+		}
+
+		let mut environment = self.environment.borrow_mut();
+		let type_variable = environment.type_variable_mut(index);
+		type_variable.binding = Some(bound_type);
+		type_variable.origin = Some(origin);
+
+		Ok(())
+	}
+
+	// Whether the variable appears in `bound_type`, lowering the level of the variables that do not to its level
+	// so they are not generalised while it is still free.
+	fn occurs(&self, index: usize, level: usize, bound_type: &Type) -> bool {
+		match self.resolve(bound_type) {
+			Type::Variable(other) if other == index => true,
+			Type::Variable(other) => {
+				let mut environment = self.environment.borrow_mut();
+				let other = environment.type_variable_mut(other);
+				other.level = other.level.min(level);
+
+				false
+			},
+			Type::Composite(CompositeType::Array(element)) => self.occurs(index, level, &element),
+			Type::Composite(CompositeType::Function { parameters, result }) => {
+				parameters.iter().any(|parameter| self.occurs(index, level, parameter)) || self.occurs(index, level, &result)
+			},
+			_ => false
+		}
+	}
+
+	// Requires `constrained_type` to be one of the types `constraint` allows.
+	pub(super) fn constrain(&self, constrained_type: &Type, constraint: Constraint, span: Span) -> Result<(), Conflict> {
+		match self.resolve(constrained_type) {
+			Type::Variable(index) => {
+				let mut environment = self.environment.borrow_mut();
+				let type_variable = environment.type_variable_mut(index);
+
+				if constraint < type_variable.constraint {
+					type_variable.constraint = constraint;
+					type_variable.constraint_origin = Some(span);
+				}
+
+				Ok(())
+			},
+			found if constraint.allows(&found) => Ok(()),
+			found => Err(Conflict::Constraint {
+				constraint,
+				origin: Some(span),
+				found
+			})
+		}
+	}
+
+	// Reports when `found` can not be used where `expected` is wanted, pointing at where each got its type.
+	pub(super) fn expect_type(&self, found: &Type, expected: &Type, span: Span) -> bool {
+		let expected_origin = self.origin(expected);
+		let found_origin = self.origin(found);
+
+		let Err(conflict) = self.unify(expected, found, Origin::Inference(span)) else {
+			return true;
+		};
+
+		let expected = self.resolve_fully(expected);
+		let found = self.resolve_fully(found);
+
+		let diagnostic = match conflict {
+			Conflict::Mismatch => {
+				let mut diagnostic = Diagnostic::error("E0304", "mismatched types", span)
+					.with_primary_label(format!("expected `{}`, found `{}`", expected, found));
+
+				for (origin, origin_type) in [(expected_origin, &expected), (found_origin, &found)] {
+					if let Some(origin) = origin.filter(|origin| origin.span() != span) {
+						diagnostic = diagnostic.with_label(origin.span(), origin.label(origin_type));
+					}
+				}
+
+				diagnostic
+			},
+			conflict => self.conflict_diagnostic(conflict, span)
+		};
+
+		self.report(diagnostic);
+
+		false
+	}
+
+	// The diagnostic for conflicts other than plain mismatches, which are explained best where they are found.
+	pub(super) fn conflict_diagnostic(&self, conflict: Conflict, span: Span) -> Diagnostic {
+		match conflict {
+			Conflict::Constraint { constraint, origin, found } => {
+				let found = self.resolve_fully(&found);
+				let mut diagnostic = Diagnostic::error("E0309", format!("`{}` is not {}", found, constraint.description()), span)
+					.with_primary_label(format!("this is of type `{}`", found));
+
+				if let Some(origin) = origin.filter(|origin| *origin != span) {
+					diagnostic = diagnostic.with_label(origin, format!("required to be {} by this", constraint.description()));
+				}
+
+				diagnostic
+			},
+			Conflict::Infinite => {
+				Diagnostic::error("E0310", "cannot infer a type that contains itself", span)
+					.with_primary_label("the type of this would have to contain itself")
+			},
+			Conflict::Mismatch => {
+				Diagnostic::error("E0304", "mismatched types", span)
+					.with_primary_label("these types do not match")
+			}
+		}
+	}
+
+	// Makes the variables of `generalised_type` that are free and deeper than `level` generic.
+	pub(super) fn generalise(&self, generalised_type: Type, level: usize) -> TypeScheme {
+		let mut variables = Vec::new();
+		self.free_variables(&generalised_type, level, &mut variables);
+
+		TypeScheme::new(variables, generalised_type)
+	}
+
+	// Keeps the variables of `lowered_type` that are deeper than `level` from being generalised with it later.
+	pub(super) fn lower_levels(&self, lowered_type: &Type, level: usize) {
+		let mut variables = Vec::new();
+		self.free_variables(lowered_type, level, &mut variables);
+
+		for index in variables {
+			self.environment.borrow_mut().type_variable_mut(index).level = level;
+		}
+	}
+
+	fn free_variables(&self, free_type: &Type, level: usize, variables: &mut Vec<usize>) {
+		match self.resolve(free_type) {
+			Type::Variable(index) if self.environment.borrow().type_variable(index).level > level && !variables.contains(&index) => {
+				variables.push(index);
+			},
+			Type::Composite(CompositeType::Array(element)) => self.free_variables(&element, level, variables),
+			Type::Composite(CompositeType::Function { parameters, result }) => {
+				for parameter in &parameters {
+					self.free_variables(parameter, level, variables);
+				}
+
+				self.free_variables(&result, level, variables);
+			},
+			_ => ()
+		}
+	}
+
+	// Gives every use of a generic scheme fresh variables of its own, with the same constraints.
+	pub(super) fn instantiate(&self, scheme: &TypeScheme) -> Type {
+		if scheme.variables().is_empty() {
+			return scheme.body().clone();
+		}
+
+		let substitution = scheme.variables().iter()
+			.map(|&index| {
+				let variable = self.fresh_variable();
+
+				if let Type::Variable(fresh) = variable {
+					let mut environment = self.environment.borrow_mut();
+					let generic = environment.type_variable(index).clone();
+					let fresh = environment.type_variable_mut(fresh);
+					fresh.constraint = generic.constraint;
+					fresh.constraint_origin = generic.constraint_origin;
+				}
+
+				(index, variable)
+			})
+			.collect::<Vec<_>>();
+
+		self.substitute(scheme.body(), &substitution)
+	}
+
+	fn substitute(&self, substituted_type: &Type, substitution: &[(usize, Type)]) -> Type {
+		match substituted_type {
+			Type::Variable(index) => {
+				if let Some((_, variable)) = substitution.iter().find(|(generic, _)| generic == index) {
+					return variable.clone();
+				}
+
+				// Bound variables are kept where possible so conflicts with them can still be explained,
+				// those whose binding changes are replaced by a variable with the same origin.
+				let (binding, origin) = {
+					let environment = self.environment.borrow();
+					let type_variable = environment.type_variable(*index);
+					(type_variable.binding.clone(), type_variable.origin)
+				};
+
+				match (binding, origin) {
+					(Some(binding), Some(origin)) if self.mentions(&binding, substitution) => {
+						let binding = self.substitute(&binding, substitution);
+						self.variable_bound_to(binding, origin)
+					},
+					(Some(binding), None) if self.mentions(&binding, substitution) => self.substitute(&binding, substitution),
+					_ => substituted_type.clone()
+				}
+			},
+			Type::Composite(CompositeType::Array(element)) => Type::array(self.substitute(element, substitution)),
+			Type::Composite(CompositeType::Function { parameters, result }) => Type::function(
+				parameters.iter().map(|parameter| self.substitute(parameter, substitution)).collect(),
+				self.substitute(result, substitution)
+			),
+			_ => substituted_type.clone()
+		}
+	}
+
+	fn mentions(&self, mentioning_type: &Type, substitution: &[(usize, Type)]) -> bool {
+		match self.resolve(mentioning_type) {
+			Type::Variable(index) => substitution.iter().any(|(generic, _)| *generic == index),
+			Type::Composite(CompositeType::Array(element)) => self.mentions(&element, substitution),
+			Type::Composite(CompositeType::Function { parameters, result }) => {
+				parameters.iter().any(|parameter| self.mentions(parameter, substitution)) || self.mentions(&result, substitution)
+			},
+			_ => false
+		}
+	}
+}
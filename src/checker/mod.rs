@@ -0,0 +1,211 @@
+mod environment;
+mod inference;
+mod expression;
+mod statement;
+
+use std::rc::Rc;
+use std::cell::{
+	Cell,
+	RefCell
+};
+use crate::syntax::{
+	Syntax,
+	FunctionDeclaration,
+	StructDeclaration
+};
+use crate::types::Type;
+use crate::diagnostic::Diagnostic;
+
+pub use environment::TypeEnvironment;
+use inference::TypeVariable;
+
+// Checks the types of a whole program before it runs, returning every error found.
+// The declarations of a program that checks are added to the environment, so later code can use them.
+pub fn check_syntax(syntax: &Syntax, environment: &mut TypeEnvironment) -> Vec<Diagnostic> {
+	let checker = Checker::new(environment.clone());
+
+	match syntax {
+		Syntax::Program(program) => {
+			checker.check_statements(program.statements());
+		}
+	}
+
+	// Function bodies are checked late, put their errors back in source order.
+	let mut diagnostics = checker.diagnostics.take();
+	diagnostics.sort_by_key(|diagnostic| diagnostic.primary_label().span().start());
+
+	if diagnostics.is_empty() {
+		*environment = checker.environment.into_inner();
+	}
+
+	diagnostics
+}
+
+// A function declared in a statement list whose body has not been checked yet.
+struct PendingFunction<'a> {
+	declaration: Rc<FunctionDeclaration<'a>>,
+	parameters: Vec<Type>,
+	result: Type,
+	// The level and scope the function is declared in.
+	level: usize,
+	scope: usize,
+	// Set while the body is being checked, calls from inside it are recursive.
+	checking: bool
+}
+
+// A struct declared in a statement list whose field types have not been resolved yet.
+struct PendingStruct<'a> {
+	declaration: Rc<StructDeclaration<'a>>,
+	// Set while its fields are being resolved, a field naming it again makes it contain itself.
+	resolving: bool
+}
+
+struct Checker<'a> {
+	environment: RefCell<TypeEnvironment>,
+	pending_functions: RefCell<Vec<PendingFunction<'a>>>,
+	pending_structs: RefCell<Vec<PendingStruct<'a>>>,
+	// How many function bodies deep the checker is, see `TypeVariable`.
+	level: Cell<usize>,
+	// The result types of the functions whose bodies are being checked, innermost last.
+	result_types: RefCell<Vec<Type>>,
+	diagnostics: RefCell<Vec<Diagnostic>>
+}
+
+impl Checker<'_> {
+	fn new(environment: TypeEnvironment) -> Self {
+		Self {
+			environment: RefCell::new(environment),
+			pending_functions: RefCell::new(Vec::new()),
+			pending_structs: RefCell::new(Vec::new()),
+			level: Cell::new(0),
+			result_types: RefCell::new(Vec::new()),
+			diagnostics: RefCell::new(Vec::new())
+		}
+	}
+
+	fn report(&self, diagnostic: Diagnostic) {
+		self.diagnostics.borrow_mut().push(diagnostic);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::lexing::{
+		Lexer,
+		Token
+	};
+	use crate::parser::Parser;
+	use crate::diagnostic::SourceMap;
+
+	fn check(code: &str) -> Vec<&'static str> {
+		diagnostics(code).iter().map(|diagnostic| diagnostic.code()).collect()
+	}
+
+	fn diagnostics(code: &str) -> Vec<Diagnostic> {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", code);
+		let tokens: Vec<Token> = Lexer::new(source_map.file(file)).map(|result| result.unwrap_or_else(|_| panic!("`{}` failed to lex", code))).collect();
+
+		let parser = Parser::new(&tokens);
+		let (syntax, diagnostics) = parser.parse();
+		assert!(diagnostics.is_empty(), "`{}` failed to parse", code);
+
+		check_syntax(&syntax, &mut TypeEnvironment::new())
+	}
+
+	#[test]
+	fn operands_must_fit_their_operator() {
+		assert_eq!(check("1 + 2 * 3.5 - 4 % 2; \"a\" + \"b\"; 1 < 2.5 && 'a' <= 'b'; -1.5; !true; 0..10"), Vec::<&str>::new());
+		assert_eq!(check("\"a\" * 2.5"), vec!["E0302"]);
+		assert_eq!(check("1 && true"), vec!["E0304"]);
+		assert_eq!(check("-\"a\"; 1..2.5; 'a' == 1"), vec!["E0303", "E0302", "E0302"]);
+	}
+
+	#[test]
+	fn annotations_are_enforced() {
+		assert_eq!(check("let a: Float = 1.5; let b: Boolean = a > 1; let c = a; c = 2.5"), Vec::<&str>::new());
+		assert_eq!(check("let a: Integer = 1.5; let b: Number = 1; let c = 1; c = \"c\""), vec!["E0304", "E0300", "E0304"]);
+	}
+
+	#[test]
+	fn functions_are_checked_against_their_signature() {
+		assert_eq!(check("fn f(a: Integer, b) -> Integer { if b { return a } a * 2 } f(1, true) + f(2, false)"), Vec::<&str>::new());
+		assert_eq!(check("fn f(a: Integer) -> String { a } f(\"a\"); f(); 1(2)"), vec!["E0304", "E0304", "E0306", "E0305"]);
+		assert_eq!(check("fn f() -> Integer { while true { return 1 } }"), vec!["E0308"]);
+		assert_eq!(check("fn f() -> Integer { if true { return 1 } else { return 2 } }"), Vec::<&str>::new());
+		assert_eq!(check("fn f(c) { if c { 1 } else if !c { 2 } else { 3 } } f(true) + 1; let a = 0; while a < 1 { if true { a = 1 } else { [1].pop(); } }"), Vec::<&str>::new());
+		assert_eq!(check("fn f(c) { if c { 1 } else { \"s\" } } fn g(c) { if c { 1 } else if !c { 2 } else { } } if true { 1 } else { \"a\" }"), vec!["E0304", "E0304"]);
+	}
+
+	#[test]
+	fn functions_see_globals_declared_after_them_but_not_their_caller() {
+		assert_eq!(check("fn f() { g + 1 } let g = 1; f()"), Vec::<&str>::new());
+		assert_eq!(check("{ let local = 1; fn f() { local } }"), vec!["E0301"]);
+	}
+
+	#[test]
+	fn functions_and_parameters_are_declared_once() {
+		assert_eq!(check("fn f(a, b) { a } fn g() { fn f() { 1 } f() } { fn g() { 2 } } f(1, 2) + g()"), Vec::<&str>::new());
+		assert_eq!(check("fn f(a, a) { a } f(1, \"s\"); fn g() { 1 } fn g() { 2 }"), vec!["E0320", "E0320"]);
+	}
+
+	#[test]
+	fn unannotated_code_is_inferred() {
+		assert_eq!(check("fn double(n) { n * 2 } let a = double(4); a = a + 1; fn f(b) { if b { 1 } else { 2 } } f(true) * 2"), Vec::<&str>::new());
+		assert_eq!(check("fn double(n) { n * 2 } double(1.5); let a = 1; a = \"a\""), vec!["E0304", "E0304"]);
+		assert_eq!(check("fn f(x) { if x { return 1 } }; fn g(x) { -x } g(\"a\"); fn h(x) { x(x) }"), vec!["E0308", "E0309", "E0310"]);
+	}
+
+	#[test]
+	fn functions_are_generic_where_nothing_fixes_their_types() {
+		assert_eq!(check("fn id(x) { x } id(1) + 1; id(\"a\") + \"b\"; fn add(a, b) { a + b } add(1.5, 2.5); add(\"a\", \"b\")"), Vec::<&str>::new());
+		assert_eq!(check("fn apply(f, x) { f(x) } fn inc(n) { n + 1 } apply(inc, 1) + 1; apply(inc, true)"), vec!["E0304"]);
+		assert_eq!(check("fn add(a, b) { a + b } add(true, false); add(1, \"a\")"), vec!["E0309", "E0304"]);
+		assert_eq!(check("fn id(x) { x } let same = id; same(1) + 1; same(\"a\") + \"b\"; let again = same; again(true) && true"), Vec::<&str>::new());
+		assert_eq!(check("fn id(x) { x } fn inc(n) { n + 1 } let same = id; same = inc; id = inc; let n = inc; n = id"), vec!["E0318", "E0318"]);
+		assert_eq!(check("let a = []; fn f() { a.push(1) } a.push(\"a\"); fn g(x) { x(1); let h = x; h(\"a\") }"), vec!["E0304", "E0304"]);
+	}
+
+	#[test]
+	fn types_still_being_inferred_are_not_named() {
+		let code = "fn f(a) { a.len() } f(5)";
+		let diagnostics = diagnostics(code);
+		let labels = diagnostics[0].secondary_labels();
+
+		assert_eq!(diagnostics[0].primary_label().message(), "expected `[_]`, found `Integer`");
+		assert_eq!((&code[labels[0].span().start()..labels[0].span().end()], labels[0].message()), ("a", "inferred to be `[_]` here"));
+	}
+
+	#[test]
+	fn structs_are_checked_field_by_field() {
+		assert_eq!(check("struct P { x: Float, y: Float } let p = P { y: 1.0, x: 2.0 }; p.x = p.y * 2.0; fn x(p) { p.x } x(p) + 1.0"), Vec::<&str>::new());
+		assert_eq!(check("struct P { x: Float, x: Float } P { x: 1 }; P { y: 1.0 }"), vec!["E0314", "E0304", "E0313", "E0312"]);
+		assert_eq!(check("struct A { x: Integer } struct B { x: Integer } fn f(v) { v.x } let a = A { x: 1 }; a.y; true.x; a.x = 1.5"), vec!["E0315", "E0312", "E0312", "E0304"]);
+	}
+
+	#[test]
+	fn fields_of_several_structs_need_an_annotation() {
+		let notes = |code| diagnostics(code)[0].notes().to_vec();
+
+		assert_eq!(notes("struct A { x: Integer } struct B { x: Integer } fn f(v) { v.x }"), vec!["`A` and `B` both have a field `x`, annotate the type of this"]);
+		assert_eq!(notes("struct A { x: Integer } struct B { x: Integer } struct C { x: Integer } fn f(v) { v.x }"), vec!["`A`, `B` and `C` all have a field `x`, annotate the type of this"]);
+	}
+
+	#[test]
+	fn structs_can_name_structs_declared_after_them() {
+		assert_eq!(check("struct P { q: Q, r: [R] } struct Q { r: R } struct R { y: Integer } P { q: Q { r: R { y: 1 } }, r: [] }.q.r.y + 1"), Vec::<&str>::new());
+		assert_eq!(check("struct P { x: P } struct A { b: B } struct B { a: [A] } { struct C { x: Integer } } struct D { c: C }"), vec!["E0319", "E0319", "E0300"]);
+		assert_eq!(check("struct P { x: Integer } { struct P { y: Integer } } struct P { y: Integer } P { x: 1 }"), vec!["E0320"]);
+	}
+
+	#[test]
+	fn arrays_hold_elements_of_one_type() {
+		assert_eq!(check("let a: [Float] = [1.5]; a.push(a[0] * 2.0); let b = a[0..1]; b[0] = a.pop() + b.len() * 1.0; fn first(xs) { xs[0] } first([true]) && true"), Vec::<&str>::new());
+		assert_eq!(check("let a = [1, true]; a[1.5]; a[0..1] = 1; 1[0]; a.len; a.pop(1)"), vec!["E0304", "E0304", "E0304", "E0316", "E0312", "E0306"]);
+		assert_eq!(check("for x in [1.5] { x * 2.0 } for row in [[true]] { for cell in row { cell && true } }"), Vec::<&str>::new());
+		assert_eq!(check("for x in [1.5] { x + \"a\" } for x in true { }"), vec!["E0302", "E0307"]);
+		assert_eq!(check("fn f(s: String) { for c in s { c == 'a' } } fn g(xs: [Integer]) { for x in xs { x + 1 } } f(\"ab\"); g([1])"), Vec::<&str>::new());
+		assert_eq!(check("fn f(s) { for c in s { c + 1 } } f(\"ab\"); fn g(s) { for c in s { return c } 0 } g(\"ab\") + 1"), vec!["E0317", "E0317"]);
+	}
+}
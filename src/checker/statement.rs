@@ -0,0 +1,437 @@
+use std::rc::Rc;
+use crate::syntax::{
+	Statement,
+	VariableDeclaration,
+	Block,
+	ElseBranch,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	FunctionDeclaration,
+	StructDeclaration,
+	ReturnStatement
+};
+use crate::types::{
+	Type,
+	TypeScheme,
+	PrimitiveType,
+	CompositeType
+};
+use crate::diagnostic::{
+	Diagnostic,
+	Span
+};
+use crate::checker::{
+	Checker,
+	PendingFunction,
+	PendingStruct
+};
+use crate::checker::inference::Origin;
+
+// What is known about how a statement finishes, mirroring `Flow` in the evaluator.
+pub enum Outcome {
+	// With the type of the value it evaluates to, if any.
+	Value(Option<Type>),
+	// An `if` whose branches have values of different types, which is an error once a function returns its value.
+	Conflict(Diagnostic),
+	// `break`, `continue` and `return` leave the statements after them unreached.
+	Diverges
+}
+
+impl<'a> Checker<'a> {
+	// Structs and functions are declared up front as they are when running. Their bodies are checked when they are first used,
+	// or after the other statements when they are not, so they see the global variables that exist by the time they can be called.
+	pub(super) fn check_statements(&self, statements: &[Statement<'a>]) -> Outcome {
+		// Every struct of the list is known before any field type is resolved, so fields can name later structs.
+		for (index, statement) in statements.iter().enumerate() {
+			if let Statement::Struct(struct_declaration) = statement {
+				let first = statements[..index].iter().find_map(|earlier| match earlier {
+					Statement::Struct(earlier) if earlier.name() == struct_declaration.name() => Some(earlier.name_span()),
+					_ => None
+				});
+
+				if let Some(first) = first {
+					self.report_duplicate("struct", struct_declaration.name(), struct_declaration.name_span(), first);
+					continue;
+				}
+
+				self.pending_structs.borrow_mut().push(PendingStruct {
+					declaration: Rc::clone(struct_declaration),
+					resolving: false
+				});
+			}
+		}
+
+		loop {
+			let next = self.pending_structs.borrow().first().map(|pending| Rc::clone(&pending.declaration));
+
+			match next {
+				Some(struct_declaration) => self.declare_struct(&struct_declaration),
+				None => break
+			}
+		}
+
+		for (index, statement) in statements.iter().enumerate() {
+			if let Statement::Function(function_declaration) = statement {
+				let first = statements[..index].iter().find_map(|earlier| match earlier {
+					Statement::Function(earlier) if earlier.name() == function_declaration.name() => Some(earlier.signature()),
+					_ => None
+				});
+
+				match first {
+					Some(first) => self.report_duplicate("function", function_declaration.name(), function_declaration.signature(), first),
+					None => self.declare_function(function_declaration)
+				}
+			}
+		}
+
+		let mut outcome = Outcome::Value(None);
+
+		for statement in statements {
+			let statement_outcome = self.check_statement(statement);
+
+			if !matches!(outcome, Outcome::Diverges) {
+				outcome = statement_outcome;
+			}
+		}
+
+		for statement in statements {
+			if let Statement::Function(function_declaration) = statement {
+				let pending = self.pending_functions.borrow().iter()
+					.position(|function| function.declaration.signature() == function_declaration.signature());
+
+				if let Some(index) = pending {
+					self.check_pending_function(index);
+				}
+			}
+		}
+
+		outcome
+	}
+
+	fn check_statement(&self, statement: &Statement<'a>) -> Outcome {
+		match statement {
+			Statement::Expression(expression_statement) => {
+				let expression_type = self.check_expression(expression_statement.expression());
+				Outcome::Value((!expression_statement.terminated()).then_some(expression_type))
+			},
+			Statement::VariableDeclaration(variable_declaration) => {
+				self.check_variable_declaration(variable_declaration);
+				Outcome::Value(None)
+			},
+			Statement::Block(block) => self.check_block(block),
+			Statement::If(if_statement) => self.check_if_statement(if_statement),
+			Statement::While(while_statement) => self.check_while_statement(while_statement),
+			Statement::For(for_statement) => self.check_for_statement(for_statement),
+			Statement::Break |
+			Statement::Continue => Outcome::Diverges,
+			// Checked by `check_statements` once the other statements are, unless used before.
+			Statement::Function(_) => Outcome::Value(None),
+			Statement::Return(return_statement) => self.check_return_statement(return_statement),
+			// Declared by `check_statements`.
+			Statement::Struct(_) => Outcome::Value(None)
+		}
+	}
+
+	// Without an annotation the variable takes the type of its value.
+	// Variables are never generic, as they can be assigned values of a single type.
+	// A variable holding a function is as generic as the function, as in `let same = id`.
+	// The value is checked a level deeper for that, like a function body.
+	fn check_variable_declaration(&self, variable_declaration: &VariableDeclaration<'a>) {
+		let level = self.level.get();
+		self.level.set(level + 1);
+		let value = self.check_expression(variable_declaration.value());
+		self.level.set(level);
+
+		let span = variable_declaration.value().span();
+
+		let variable_type = match variable_declaration.annotation() {
+			Some(annotation) => {
+				let annotated_type = self.annotated_type(Some(annotation));
+				self.expect_type(&value, &annotated_type, span);
+
+				annotated_type
+			},
+			None => self.variable_bound_to(value, Origin::Inference(span))
+		};
+
+		let scheme = if matches!(self.resolve(&variable_type), Type::Composite(CompositeType::Function { .. })) {
+			self.generalise(variable_type, level)
+		} else {
+			self.lower_levels(&variable_type, level);
+			TypeScheme::monomorphic(variable_type)
+		};
+
+		self.environment.borrow_mut().define(variable_declaration.name(), scheme, variable_declaration.name_span());
+	}
+
+	fn check_block(&self, block: &Block<'a>) -> Outcome {
+		self.environment.borrow_mut().push_scope();
+		let outcome = self.check_statements(block.statements());
+		self.environment.borrow_mut().pop_scope();
+
+		outcome
+	}
+
+	// An `if` with an `else` has a value when the types of both branches unify, a branch without one counts as `()`.
+	fn check_if_statement(&self, if_statement: &IfStatement<'a>) -> Outcome {
+		self.check_expression_against(if_statement.condition(), &Type::Primitive(PrimitiveType::Boolean));
+
+		let consequence = self.check_block(if_statement.consequence());
+		let alternative = match if_statement.alternative() {
+			Some(ElseBranch::If(else_if_statement)) => self.check_if_statement(else_if_statement),
+			Some(ElseBranch::Block(block)) => self.check_block(block),
+			None => return Outcome::Value(None)
+		};
+
+		match (consequence, alternative) {
+			(Outcome::Diverges, outcome) |
+			(outcome, Outcome::Diverges) => outcome,
+			(conflict @ Outcome::Conflict(_), _) |
+			(_, conflict @ Outcome::Conflict(_)) => conflict,
+			(Outcome::Value(None), Outcome::Value(None)) => Outcome::Value(None),
+			(Outcome::Value(consequence), Outcome::Value(alternative)) => {
+				let consequence = consequence.unwrap_or(Type::Primitive(PrimitiveType::Unit));
+				let alternative = alternative.unwrap_or(Type::Primitive(PrimitiveType::Unit));
+				let consequence_span = final_expression_span(if_statement.consequence());
+				let span = consequence_span.unwrap_or(if_statement.condition().span());
+
+				if self.try_unify(&consequence, &alternative, Origin::Inference(span)) {
+					return Outcome::Value(Some(consequence));
+				}
+
+				let consequence = self.resolve_fully(&consequence);
+				let alternative = self.resolve_fully(&alternative);
+				let alternative_span = match if_statement.alternative() {
+					Some(ElseBranch::If(else_if_statement)) => final_expression_span(else_if_statement.consequence()),
+					Some(ElseBranch::Block(block)) => final_expression_span(block),
+					None => None
+				};
+
+				let alternative_span = alternative_span.unwrap_or(span);
+				let mut diagnostic = Diagnostic::error("E0304", "`if` and `else` have incompatible types", alternative_span)
+					.with_primary_label(format!("expected `{}`, found `{}`", consequence, alternative))
+					.with_note("a function returning the value of an `if` needs the same type from every branch");
+
+				if let Some(consequence_span) = consequence_span.filter(|consequence_span| *consequence_span != alternative_span) {
+					diagnostic = diagnostic.with_label(consequence_span, format!("this is of type `{}`", consequence));
+				}
+
+				Outcome::Conflict(diagnostic)
+			}
+		}
+	}
+
+	fn check_while_statement(&self, while_statement: &WhileStatement<'a>) -> Outcome {
+		self.check_expression_against(while_statement.condition(), &Type::Primitive(PrimitiveType::Boolean));
+		self.check_block(while_statement.body());
+
+		Outcome::Value(None)
+	}
+
+	fn check_for_statement(&self, for_statement: &ForStatement<'a>) -> Outcome {
+		let iterable = self.check_expression(for_statement.iterable());
+
+		let element_type = match self.resolve(&iterable) {
+			Type::Primitive(PrimitiveType::Range) => Type::Primitive(PrimitiveType::Integer),
+			Type::Primitive(PrimitiveType::String) => Type::Primitive(PrimitiveType::Character),
+			Type::Composite(CompositeType::Array(element_type)) => *element_type,
+			// The type of the loop variable depends on what is iterated over, so that must be known by now.
+			Type::Variable(_) => {
+				self.report(
+					Diagnostic::error("E0317", "type annotation needed", for_statement.iterable().span())
+						.with_primary_label("the type of this is not known yet")
+						.with_note("annotate it as a `Range`, a `String` or an array such as `[Integer]`")
+				);
+
+				Type::Unknown
+			},
+			Type::Unknown => Type::Unknown,
+			iterable => {
+				let iterable = self.resolve_fully(&iterable);
+
+				self.report(
+					Diagnostic::error("E0307", format!("cannot iterate over `{}`", iterable), for_statement.iterable().span())
+						.with_primary_label(format!("this is of type `{}`", iterable))
+						.with_note("`for` loops go over a `Range` such as `0..10`, the characters of a `String` or the elements of an array")
+				);
+
+				Type::Unknown
+			}
+		};
+
+		self.environment.borrow_mut().push_scope();
+		self.environment.borrow_mut().define(for_statement.variable(), TypeScheme::monomorphic(element_type), for_statement.variable_span());
+		self.check_block(for_statement.body());
+		self.environment.borrow_mut().pop_scope();
+
+		Outcome::Value(None)
+	}
+
+	fn check_return_statement(&self, return_statement: &ReturnStatement<'a>) -> Outcome {
+		let value = match return_statement.value() {
+			Some(value) => self.check_expression(value),
+			None => Type::Primitive(PrimitiveType::Unit)
+		};
+
+		// The parser only allows `return` inside functions.
+		let result_type = self.result_types.borrow().last().cloned().unwrap_or(Type::Unknown);
+		let span = return_statement.value().map_or(return_statement.span(), |value| value.span());
+		self.expect_type(&value, &result_type, span);
+
+		Outcome::Diverges
+	}
+
+	// Resolves the fields of a pending struct, first declaring the pending structs they name.
+	pub(super) fn declare_struct(&self, struct_declaration: &Rc<StructDeclaration<'a>>) {
+		self.set_struct_resolving(struct_declaration);
+
+		let mut fields: Vec<(String, Type)> = Vec::new();
+
+		for field in struct_declaration.fields() {
+			let field_type = field.annotation().map_or(Type::Unknown, |annotation| self.annotated_type(Some(annotation)));
+
+			if fields.iter().any(|(name, _)| name == field.name()) {
+				let first = struct_declaration.fields().iter().find(|first| first.name() == field.name()).unwrap_or(field);
+
+				self.report(
+					Diagnostic::error("E0314", format!("field `{}` is declared more than once", field.name()), field.span())
+						.with_primary_label("declared again here")
+						.with_label(first.span(), "first declared here")
+				);
+
+				continue;
+			}
+
+			fields.push((field.name().to_string(), field_type));
+		}
+
+		let struct_type = Type::Composite(CompositeType::UserDefined {
+			name: struct_declaration.name().to_string(),
+			fields
+		});
+
+		self.environment.borrow_mut().define_struct(struct_declaration.name(), struct_type);
+		self.pending_structs.borrow_mut().retain(|pending| !Rc::ptr_eq(&pending.declaration, struct_declaration));
+	}
+
+	fn set_struct_resolving(&self, struct_declaration: &Rc<StructDeclaration<'a>>) {
+		for pending in self.pending_structs.borrow_mut().iter_mut() {
+			if Rc::ptr_eq(&pending.declaration, struct_declaration) {
+				pending.resolving = true;
+			}
+		}
+	}
+
+	// Declares the function with the types of its signature, to be inferred where it has no annotations.
+	// Those are made one level deeper than the declaration so they can be generalised once the body is checked.
+	fn declare_function(&self, function_declaration: &Rc<FunctionDeclaration<'a>>) {
+		let level = self.level.get();
+		self.level.set(level + 1);
+
+		for (index, parameter) in function_declaration.parameters().iter().enumerate() {
+			if let Some(first) = function_declaration.parameters()[..index].iter().find(|first| first.name() == parameter.name()) {
+				self.report_duplicate("parameter", parameter.name(), parameter.span(), first.span());
+			}
+		}
+
+		let parameters = function_declaration.parameters().iter()
+			.map(|parameter| self.annotated_type(parameter.annotation()))
+			.collect::<Vec<_>>();
+		let result = self.annotated_type(function_declaration.result());
+
+		self.level.set(level);
+
+		let function_type = Type::function(parameters.clone(), result.clone());
+		let scope = self.environment.borrow().current_scope();
+		self.environment.borrow_mut().define(function_declaration.name(), TypeScheme::monomorphic(function_type), function_declaration.signature());
+
+		self.pending_functions.borrow_mut().push(PendingFunction {
+			declaration: Rc::clone(function_declaration),
+			parameters,
+			result,
+			level,
+			scope,
+			checking: false
+		});
+	}
+
+	// Two structs or functions of one block, or two parameters of one function, cannot share a name.
+	fn report_duplicate(&self, kind: &str, name: &str, span: Span, first: Span) {
+		self.report(
+			Diagnostic::error("E0320", format!("{} `{}` is declared more than once", kind, name), span)
+				.with_primary_label("declared again here")
+				.with_label(first, "first declared here")
+		);
+	}
+
+	// The body runs in a frame of its own, as it does when the function is called.
+	// Once it is checked, the type variables only the function's type still has make it generic.
+	pub(super) fn check_pending_function(&self, index: usize) {
+		let (function_declaration, parameters, result, level, scope) = {
+			let mut pending_functions = self.pending_functions.borrow_mut();
+			let function = &mut pending_functions[index];
+			function.checking = true;
+
+			(Rc::clone(&function.declaration), function.parameters.clone(), function.result.clone(), function.level, function.scope)
+		};
+
+		let function_type = Type::function(parameters.clone(), result.clone());
+		let outer_level = self.level.replace(level + 1);
+
+		self.environment.borrow_mut().push_frame();
+		self.environment.borrow_mut().define(function_declaration.name(), TypeScheme::monomorphic(function_type.clone()), function_declaration.signature());
+
+		for (parameter, parameter_type) in function_declaration.parameters().iter().zip(parameters) {
+			self.environment.borrow_mut().define(parameter.name(), TypeScheme::monomorphic(parameter_type), parameter.span());
+		}
+
+		self.result_types.borrow_mut().push(result.clone());
+		let outcome = self.check_statements(function_declaration.body().statements());
+		self.result_types.borrow_mut().pop();
+		self.environment.borrow_mut().pop_frame();
+
+		self.check_function_result(&function_declaration, outcome, &result);
+		self.level.set(outer_level);
+
+		let scheme = self.generalise(function_type, level);
+		self.environment.borrow_mut().redefine(scope, function_declaration.name(), scheme, function_declaration.signature());
+		self.pending_functions.borrow_mut().retain(|function| !Rc::ptr_eq(&function.declaration, &function_declaration));
+	}
+
+	fn check_function_result(&self, function_declaration: &FunctionDeclaration<'a>, outcome: Outcome, result: &Type) {
+		let result_span = function_declaration.result().map_or(function_declaration.signature(), |result| result.span());
+
+		match outcome {
+			Outcome::Diverges => (),
+			Outcome::Conflict(diagnostic) => self.report(diagnostic),
+			Outcome::Value(Some(value)) => {
+				// Point at the final expression when there is one to point at.
+				let span = final_expression_span(function_declaration.body()).unwrap_or(result_span);
+				self.expect_type(&value, result, span);
+			},
+			// A function that finishes without a value returns `()`, unless it has to return something else.
+			Outcome::Value(None) => match self.resolve(result) {
+				Type::Variable(_) => {
+					self.unify(result, &Type::Primitive(PrimitiveType::Unit), Origin::Inference(result_span)).ok();
+				},
+				Type::Primitive(PrimitiveType::Unit) |
+				Type::Unknown => (),
+				result => {
+					self.report(
+						Diagnostic::error("E0308", format!("function `{}` can finish without returning a value", function_declaration.name()), result_span)
+							.with_primary_label(format!("expected to return `{}`", self.resolve_fully(&result)))
+							.with_note("end the body with the value to return, or `return` one on every path")
+					);
+				}
+			}
+		}
+	}
+}
+
+fn final_expression_span(block: &Block) -> Option<Span> {
+	match block.statements().last() {
+		Some(Statement::Expression(expression_statement)) => Some(expression_statement.expression().span()),
+		_ => None
+	}
+}
@@ -0,0 +1,95 @@
+pub enum Input {
+	File(String),
+	StandardInput
+}
+
+pub enum Command {
+	Run(Input),
+	Eval(String),
+	Check(Input),
+	Tokens(Input),
+	Repl,
+	Help
+}
+
+pub struct Arguments {
+	command: Command,
+	print_tree: bool
+}
+
+impl Arguments {
+	pub fn parse(arguments: &[String]) -> Result<Self, String> {
+		let mut print_tree = false;
+		let mut positional = Vec::new();
+		let mut options_ended = false;
+
+		for argument in arguments {
+			if options_ended {
+				positional.push(argument.as_str());
+				continue;
+			}
+
+			match argument.as_str() {
+				"--" => options_ended = true,
+				"--tree" => print_tree = true,
+				"-h" | "--help" => return Ok(Self { command: Command::Help, print_tree }),
+				// Anything else that looks like a flag is rejected, but `-` and code such as `-1` are kept.
+				_ if argument.starts_with("--") || (argument.starts_with('-') && argument.chars().nth(1).is_some_and(char::is_alphabetic)) => {
+					return Err(format!("unknown option `{}`", argument));
+				},
+				_ => positional.push(argument.as_str())
+			}
+		}
+
+		let command = match positional.as_slice() {
+			[] | ["repl"] => Command::Repl,
+			["help"] => Command::Help,
+			["run", input] => Command::Run(Self::parse_input(input)),
+			["check", input] => Command::Check(Self::parse_input(input)),
+			["tokens", input] => Command::Tokens(Self::parse_input(input)),
+			["eval", expression] => Command::Eval(expression.to_string()),
+			["run" | "check" | "tokens" | "eval", ..] => return Err(format!("`{}` expects exactly one argument", positional[0])),
+			[command, ..] => return Err(format!("unknown command `{}`", command))
+		};
+
+		Ok(Self {
+			command,
+			print_tree
+		})
+	}
+
+	fn parse_input(input: &str) -> Input {
+		if input == "-" {
+			Input::StandardInput
+		} else {
+			Input::File(input.to_string())
+		}
+	}
+
+	pub fn command(&self) -> &Command {
+		&self.command
+	}
+
+	pub fn print_tree(&self) -> bool {
+		self.print_tree
+	}
+}
+
+pub const USAGE: &str = "\
+Usage: kalf [command] [options]
+
+Commands:
+  repl           Start an interactive session (the default)
+  run <file>     Evaluate a kalf program
+  eval <code>    Evaluate the given code
+  check <file>   Check the types of a program without running it
+  tokens <file>  Print the tokens of a program with the white space and
+                 comments before each of them
+  help           Print this message
+
+Use `-` as <file> to read the program from standard input, and `--` to
+stop option parsing.
+
+Options:
+  --tree         Print the syntax tree before evaluating
+  -h, --help     Print this message";
@@ -0,0 +1,24 @@
+use std::process::ExitCode;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+	Lexing,
+	Parsing,
+	Typing,
+	Runtime,
+	Usage,
+	Io
+}
+
+impl Failure {
+	pub fn exit_code(&self) -> ExitCode {
+		ExitCode::from(match self {
+			Failure::Lexing => 1,
+			Failure::Parsing => 2,
+			Failure::Runtime => 3,
+			Failure::Typing => 4,
+			Failure::Usage => 64,
+			Failure::Io => 74
+		})
+	}
+}
@@ -0,0 +1,213 @@
+mod arguments;
+mod failure;
+mod repl;
+
+use std::fs;
+use std::io::{
+	self,
+	Read
+};
+use std::process::ExitCode;
+use std::rc::Rc;
+use crate::lexing::{
+	Token,
+	Lexer
+};
+use crate::parser::Parser;
+use crate::checker::{
+	TypeEnvironment,
+	check_syntax
+};
+use crate::syntax::Syntax;
+use crate::diagnostic::{
+	print_syntax,
+	print_tokens,
+	print_diagnostic,
+	FileId,
+	SourceMap
+};
+use crate::runtime::{
+	Environment,
+	evaluate_syntax
+};
+use arguments::{
+	Arguments,
+	Command,
+	Input,
+	USAGE
+};
+use failure::Failure;
+use repl::Session;
+
+pub fn run(arguments: &[String]) -> ExitCode {
+	let arguments = match Arguments::parse(arguments) {
+		Ok(arguments) => arguments,
+		Err(message) => {
+			eprintln!("error: {}", message);
+			eprintln!();
+			eprintln!("{}", USAGE);
+			return Failure::Usage.exit_code();
+		}
+	};
+
+	let result = match arguments.command() {
+		Command::Run(input) => read_input(input).and_then(|code| {
+			let (source_map, file) = single_file(input_name(input), &code);
+			run_code(&source_map, file, arguments.print_tree(), &mut TypeEnvironment::new(), &mut Environment::new())
+		}),
+		Command::Eval(code) => {
+			let (source_map, file) = single_file("<eval>", code);
+			run_code(&source_map, file, arguments.print_tree(), &mut TypeEnvironment::new(), &mut Environment::new())
+		},
+		Command::Check(input) => read_input(input).and_then(|code| {
+			let (source_map, file) = single_file(input_name(input), &code);
+			check_code(&source_map, file, arguments.print_tree())
+		}),
+		Command::Tokens(input) => read_input(input).and_then(|code| {
+			let (source_map, file) = single_file(input_name(input), &code);
+			print_code_tokens(&source_map, file)
+		}),
+		Command::Repl => Session::new(arguments.print_tree()).run(),
+		Command::Help => {
+			println!("{}", USAGE);
+			Ok(())
+		}
+	};
+
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(failure) => failure.exit_code()
+	}
+}
+
+fn read_input(input: &Input) -> Result<String, Failure> {
+	match input {
+		Input::File(path) => fs::read_to_string(path).map_err(|error| {
+			eprintln!("error: could not read `{}`: {}", path, error);
+			Failure::Io
+		}),
+		Input::StandardInput => {
+			let mut code = String::new();
+
+			match io::stdin().read_to_string(&mut code) {
+				Ok(_) => Ok(code),
+				Err(error) => {
+					eprintln!("error: could not read standard input: {}", error);
+					Err(Failure::Io)
+				}
+			}
+		}
+	}
+}
+
+fn input_name(input: &Input) -> &str {
+	match input {
+		Input::File(path) => path,
+		Input::StandardInput => "<stdin>"
+	}
+}
+
+// A source map holding just the given code.
+fn single_file<'a>(name: &str, code: &'a str) -> (SourceMap<'a>, FileId) {
+	let mut source_map = SourceMap::new();
+	let file = source_map.add(name, code);
+
+	(source_map, file)
+}
+
+// Lexes the whole code, reporting every unrecognised token before giving up.
+fn lex_code<'a>(lexer: Lexer<'a>, source_map: &SourceMap) -> Result<Vec<Token<'a>>, Failure> {
+	let mut tokens = Vec::new();
+	let mut failed = false;
+
+	for result in lexer {
+		match result {
+			Ok(token) => tokens.push(token),
+			Err(diagnostic) => {
+				print_diagnostic(&diagnostic, source_map);
+				failed = true;
+			}
+		}
+	}
+
+	if failed {
+		Err(Failure::Lexing)
+	} else {
+		Ok(tokens)
+	}
+}
+
+fn parse_tokens<'a>(tokens: &'a Vec<Token<'a>>, source_map: &SourceMap, print_tree: bool) -> Result<Rc<Syntax<'a>>, Failure> {
+	let parser = Parser::new(tokens);
+	let (syntax, diagnostics) = parser.parse();
+
+	if print_tree {
+		print_syntax(&syntax);
+	}
+
+	for diagnostic in &diagnostics {
+		print_diagnostic(diagnostic, source_map);
+	}
+
+	if diagnostics.is_empty() {
+		Ok(syntax)
+	} else {
+		Err(Failure::Parsing)
+	}
+}
+
+fn check_types(syntax: &Syntax, source_map: &SourceMap, type_environment: &mut TypeEnvironment) -> Result<(), Failure> {
+	let diagnostics = check_syntax(syntax, type_environment);
+
+	for diagnostic in &diagnostics {
+		print_diagnostic(diagnostic, source_map);
+	}
+
+	if diagnostics.is_empty() {
+		Ok(())
+	} else {
+		Err(Failure::Typing)
+	}
+}
+
+fn check_code(source_map: &SourceMap, file: FileId, print_tree: bool) -> Result<(), Failure> {
+	let tokens = lex_code(Lexer::new(source_map.file(file)), source_map)?;
+	let syntax = parse_tokens(&tokens, source_map, print_tree)?;
+
+	check_types(&syntax, source_map, &mut TypeEnvironment::new())
+}
+
+fn print_code_tokens(source_map: &SourceMap, file: FileId) -> Result<(), Failure> {
+	let tokens = lex_code(Lexer::new(source_map.file(file)).with_trivia(), source_map)?;
+	print_tokens(&tokens, source_map.file(file));
+
+	Ok(())
+}
+
+// Functions declared by the code outlive its evaluation inside the environment, and with them the tokens they point to.
+// The tokens are therefore leaked, which only adds up in the interactive session where every input is kept anyway.
+fn run_code<'a>(source_map: &SourceMap<'a>, file: FileId, print_tree: bool, type_environment: &mut TypeEnvironment, environment: &mut Environment<'a>) -> Result<(), Failure> {
+	let tokens: &'a Vec<Token<'a>> = Box::leak(Box::new(lex_code(Lexer::new(source_map.file(file)), source_map)?));
+	let syntax = parse_tokens(tokens, source_map, print_tree)?;
+	// Code that fails at runtime leaves both environments as they were, so later code sees none of what it declared.
+	let mut checked_environment = type_environment.clone();
+	check_types(&syntax, source_map, &mut checked_environment)?;
+	let snapshot = environment.clone();
+
+	let value = match evaluate_syntax(&syntax, environment) {
+		Ok(value) => value,
+		Err(error) => {
+			print_diagnostic(&error.to_diagnostic(), source_map);
+			*environment = snapshot;
+			return Err(Failure::Runtime);
+		}
+	};
+
+	*type_environment = checked_environment;
+
+	if let Some(value) = value {
+		println!("{}", value);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,154 @@
+use std::io::{
+	self,
+	BufRead,
+	Write
+};
+use crate::lexing::{
+	Lexer,
+	TokenKind
+};
+use crate::checker::TypeEnvironment;
+use crate::runtime::Environment;
+use crate::diagnostic::SourceMap;
+use crate::cli::run_code;
+use crate::cli::failure::Failure;
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+
+// Variables declared in the session stay available to every later input.
+pub struct Session {
+	buffer: String,
+	type_environment: TypeEnvironment,
+	environment: Environment<'static>,
+	// Every input becomes a file of its own, so labels pointing into earlier inputs show those.
+	source_map: SourceMap<'static>,
+	print_tree: bool
+}
+
+impl Session {
+	pub fn new(print_tree: bool) -> Self {
+		Self {
+			buffer: String::new(),
+			type_environment: TypeEnvironment::new(),
+			environment: Environment::new(),
+			source_map: SourceMap::new(),
+			print_tree
+		}
+	}
+
+	pub fn run(&mut self) -> Result<(), Failure> {
+		let standard_input = io::stdin();
+		let mut lines = standard_input.lock().lines();
+
+		loop {
+			self.prompt()?;
+
+			let line = match lines.next() {
+				Some(Ok(line)) => line,
+				Some(Err(error)) => {
+					eprintln!("error: could not read standard input: {}", error);
+					return Err(Failure::Io);
+				},
+				None => {
+					println!();
+					return Ok(());
+				}
+			};
+
+			if self.buffer.is_empty() {
+				match line.trim() {
+					"" => continue,
+					":quit" | ":q" => return Ok(()),
+					_ => ()
+				}
+			}
+
+			self.buffer.push_str(&line);
+			self.buffer.push('\n');
+
+			if !is_incomplete(&self.buffer) {
+				let code = std::mem::take(&mut self.buffer);
+				// Errors have already been reported, the session carries on regardless.
+				let _ = self.run_input(code);
+			}
+		}
+	}
+
+	fn run_input(&mut self, code: String) -> Result<(), Failure> {
+		// Functions declared here stay around for later inputs, so the code has to as well.
+		let code: &'static str = Box::leak(code.into_boxed_str());
+		let file = self.source_map.add(format!("<repl:{}>", self.source_map.files().len() + 1), code);
+
+		run_code(&self.source_map, file, self.print_tree, &mut self.type_environment, &mut self.environment)
+	}
+
+	fn prompt(&self) -> Result<(), Failure> {
+		print!("{}", if self.buffer.is_empty() { PROMPT } else { CONTINUATION_PROMPT });
+
+		io::stdout().flush().map_err(|error| {
+			eprintln!("error: could not write to standard output: {}", error);
+			Failure::Io
+		})
+	}
+}
+
+// Input is incomplete while it still has unclosed delimiters, the user is then asked for more lines.
+fn is_incomplete(code: &str) -> bool {
+	let mut source_map = SourceMap::new();
+	let file = source_map.add("<repl>", code);
+	let mut depth = 0usize;
+
+	for result in Lexer::new(source_map.file(file)) {
+		match result {
+			Ok(token) => match token.kind() {
+				TokenKind::OpenParenthesis |
+				TokenKind::OpenBrace |
+				TokenKind::OpenBracket |
+				TokenKind::InterpolationStart => depth += 1,
+				TokenKind::CloseParenthesis |
+				TokenKind::CloseBrace |
+				TokenKind::CloseBracket |
+				TokenKind::InterpolationEnd => depth = depth.saturating_sub(1),
+				_ => ()
+			},
+			// Strings and block comments can span lines, one still open at the end goes on in the next.
+			Err(diagnostic) if matches!(diagnostic.code(), "E0002" | "E0008") => return true,
+			// Let the regular pipeline report other lexing errors.
+			Err(_) => return false
+		}
+	}
+
+	depth > 0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn open_delimiters_strings_and_comments_ask_for_more_lines() {
+		assert!(is_incomplete("fn f() {\n"));
+		assert!(is_incomplete("let a = [1,\n"));
+		assert!(is_incomplete("let s = \"one\n"));
+		assert!(is_incomplete("let s = r#\"one\"\n"));
+		assert!(is_incomplete("\"${\n"));
+		assert!(is_incomplete("1 /* one\n"));
+		assert!(is_incomplete("1 /* one */ /* two /* three */\n"));
+		assert!(!is_incomplete("let s = \"one\ntwo\";\n"));
+		assert!(!is_incomplete("1 /* one\ntwo */\n"));
+		assert!(!is_incomplete("1 + 2)\n"));
+		assert!(!is_incomplete("let c = '';\n"));
+	}
+
+	#[test]
+	fn inputs_failing_at_runtime_declare_nothing() {
+		let mut session = Session::new(false);
+		let mut run = |code: &str| session.run_input(code.to_string());
+
+		assert!(run("let x = 1;") == Ok(()));
+		assert!(run("let x = \"s\"; let z = 5; 1 / 0") == Err(Failure::Runtime));
+		assert!(run("x + 1;") == Ok(()));
+		assert!(run("z;") == Err(Failure::Typing));
+	}
+}
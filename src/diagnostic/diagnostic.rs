@@ -0,0 +1,136 @@
+use std::fmt;
+use crate::diagnostic::Span;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+	Error,
+	#[allow(dead_code)]
+	Warning
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Severity::Error => write!(formatter, "error"),
+			Severity::Warning => write!(formatter, "warning")
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct Label {
+	span: Span,
+	message: String
+}
+
+impl Label {
+	pub fn new(span: Span, message: impl Into<String>) -> Self {
+		Self {
+			span,
+			message: message.into()
+		}
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+}
+
+#[derive(Clone, Debug)]
+struct DiagnosticContent {
+	severity: Severity,
+	code: &'static str,
+	message: String,
+	primary_label: Label,
+	secondary_labels: Vec<Label>,
+	notes: Vec<String>
+}
+
+// Boxed so results carrying a diagnostic stay small.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+	content: Box<DiagnosticContent>
+}
+
+impl Diagnostic {
+	pub fn new(severity: Severity, code: &'static str, message: impl Into<String>, span: Span) -> Self {
+		Self {
+			content: Box::new(DiagnosticContent {
+				severity,
+				code,
+				message: message.into(),
+				primary_label: Label::new(span, ""),
+				secondary_labels: Vec::new(),
+				notes: Vec::new()
+			})
+		}
+	}
+
+	pub fn error(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+		Self::new(Severity::Error, code, message, span)
+	}
+
+	// Message printed under the primary span.
+	pub fn with_primary_label(mut self, message: impl Into<String>) -> Self {
+		self.content.primary_label = Label::new(self.content.primary_label.span(), message);
+		self
+	}
+
+	pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+		self.content.secondary_labels.push(Label::new(span, message));
+		self
+	}
+
+	pub fn with_note(mut self, note: impl Into<String>) -> Self {
+		self.content.notes.push(note.into());
+		self
+	}
+
+	pub fn severity(&self) -> Severity {
+		self.content.severity
+	}
+
+	pub fn code(&self) -> &'static str {
+		self.content.code
+	}
+
+	pub fn message(&self) -> &str {
+		&self.content.message
+	}
+
+	pub fn primary_label(&self) -> &Label {
+		&self.content.primary_label
+	}
+
+	pub fn secondary_labels(&self) -> &[Label] {
+		&self.content.secondary_labels
+	}
+
+	pub fn notes(&self) -> &[String] {
+		&self.content.notes
+	}
+}
+
+// `1 argument`, `2 arguments` and so on, for messages.
+pub fn pluralise(count: usize, noun: &str) -> String {
+	if count == 1 {
+		format!("1 {}", noun)
+	} else {
+		format!("{} {}s", count, noun)
+	}
+}
+
+// Quotes every name and joins them as in "`x`, `y` and `z`".
+pub fn quote_list<T: AsRef<str>>(names: &[T]) -> String {
+	let quoted = names.iter().map(|name| format!("`{}`", name.as_ref())).collect::<Vec<_>>();
+
+	match quoted.split_last() {
+		Some((last, [])) => last.clone(),
+		Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+		None => String::new()
+	}
+}
@@ -1,3 +1,23 @@
+#[allow(clippy::module_inception)]
+mod diagnostic;
 mod printing;
+mod rendering;
+mod source_map;
+mod span;
 
-pub use printing::print_syntax;
+pub use diagnostic::{
+	Diagnostic,
+	pluralise,
+	quote_list
+};
+pub use printing::{
+	print_syntax,
+	print_tokens
+};
+pub use rendering::print_diagnostic;
+pub use source_map::{
+	FileId,
+	SourceFile,
+	SourceMap
+};
+pub use span::Span;
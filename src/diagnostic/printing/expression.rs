@@ -1,11 +1,19 @@
 use crate::syntax::{
 	Expression,
-	BinaryExpressionKind,
 	BinaryExpression,
-	UnaryExpressionKind,
 	UnaryExpression,
 	LiteralExpression,
-	ParenthesisedExpression
+	ParenthesisedExpression,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	StringPart,
+	InterpolatedStringExpression,
+	ErrorExpression
 };
 
 pub fn print_expression(expression: &Expression, indentation: usize) {
@@ -13,10 +21,101 @@ pub fn print_expression(expression: &Expression, indentation: usize) {
 		Expression::Binary(binary_expression) => print_binary_expression(binary_expression, indentation),
 		Expression::Unary(unary_expression) => print_unary_expression(unary_expression, indentation),
 		Expression::Literal(literal_expression) => print_literal_expression(literal_expression, indentation),
-		Expression::Parenthesised(parenthesised_expression) => print_parenthesised_expression(parenthesised_expression, indentation)
+		Expression::Parenthesised(parenthesised_expression) => print_parenthesised_expression(parenthesised_expression, indentation),
+		Expression::Identifier(identifier_expression) => print_identifier_expression(identifier_expression, indentation),
+		Expression::Assignment(assignment_expression) => print_assignment_expression(assignment_expression, indentation),
+		Expression::Call(call_expression) => print_call_expression(call_expression, indentation),
+		Expression::StructLiteral(struct_literal_expression) => print_struct_literal_expression(struct_literal_expression, indentation),
+		Expression::FieldAccess(field_access_expression) => print_field_access_expression(field_access_expression, indentation),
+		Expression::ArrayLiteral(array_literal_expression) => print_array_literal_expression(array_literal_expression, indentation),
+		Expression::Index(index_expression) => print_index_expression(index_expression, indentation),
+		Expression::InterpolatedString(interpolated_string_expression) => print_interpolated_string_expression(interpolated_string_expression, indentation),
+		Expression::Error(error_expression) => print_error_expression(error_expression, indentation)
 	}
 }
 
+fn print_identifier_expression(identifier_expression: &IdentifierExpression, _: usize) {
+	println!("{}", identifier_expression.name());
+}
+
+fn print_assignment_expression(assignment_expression: &AssignmentExpression, indentation: usize) {
+	println!("=");
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(assignment_expression.target(), indentation + 1);
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(assignment_expression.value(), indentation + 1);
+}
+
+fn print_call_expression(call_expression: &CallExpression, indentation: usize) {
+	println!("call");
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(call_expression.callee(), indentation + 1);
+
+	for argument in call_expression.arguments() {
+		print_indentation(indentation);
+		print!("└ ");
+		print_expression(argument, indentation + 1);
+	}
+}
+
+fn print_struct_literal_expression(struct_literal_expression: &StructLiteralExpression, indentation: usize) {
+	println!("{} {{}}", struct_literal_expression.name());
+
+	for field in struct_literal_expression.fields() {
+		print_indentation(indentation);
+		print!("└ {}: ", field.name());
+		print_expression(field.value(), indentation + 1);
+	}
+}
+
+fn print_field_access_expression(field_access_expression: &FieldAccessExpression, indentation: usize) {
+	println!(".{}", field_access_expression.field());
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(field_access_expression.object(), indentation + 1);
+}
+
+fn print_array_literal_expression(array_literal_expression: &ArrayLiteralExpression, indentation: usize) {
+	println!("array");
+
+	for element in array_literal_expression.elements() {
+		print_indentation(indentation);
+		print!("└ ");
+		print_expression(element, indentation + 1);
+	}
+}
+
+fn print_index_expression(index_expression: &IndexExpression, indentation: usize) {
+	println!("index");
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(index_expression.object(), indentation + 1);
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(index_expression.index(), indentation + 1);
+}
+
+fn print_interpolated_string_expression(interpolated_string_expression: &InterpolatedStringExpression, indentation: usize) {
+	println!("interpolation");
+
+	for part in interpolated_string_expression.parts() {
+		print_indentation(indentation);
+		print!("└ ");
+
+		match part {
+			StringPart::Text(text) => println!("{:?}", text),
+			StringPart::Expression(expression) => print_expression(expression, indentation + 1)
+		}
+	}
+}
+
+fn print_error_expression(error_expression: &ErrorExpression, _: usize) {
+	println!("<error {}..{}>", error_expression.span().start(), error_expression.span().end());
+}
+
 fn print_parenthesised_expression(parenthesised_expression: &ParenthesisedExpression, indentation: usize) {
 	print!("( ");
 	print_expression(parenthesised_expression.content(), indentation + 1);
@@ -29,22 +128,13 @@ fn print_literal_expression(literal_expression: &LiteralExpression, _: usize) {
 }
 
 fn print_unary_expression(unary_expression: &UnaryExpression, indentation: usize) {
-	match unary_expression.kind() {
-		UnaryExpressionKind::Identity => print!("+ "),
-		UnaryExpressionKind::Negation => print!("- ")
-	}
+	print!("{} ", unary_expression.kind().symbol());
 
 	print_expression(unary_expression.operand(), indentation + 1);
 }
 
 fn print_binary_expression(binary_expression: &BinaryExpression, indentation: usize) {
-	match binary_expression.kind() {
-		BinaryExpressionKind::Addition => println!("+"),
-		BinaryExpressionKind::Substraction => println!("-"),
-		BinaryExpressionKind::Multiplication => println!("*"),
-		BinaryExpressionKind::Division => println!("/"),
-		BinaryExpressionKind::Modulo => println!("%")
-	}
+	println!("{}", binary_expression.kind().symbol());
 
 	print_indentation(indentation);
 	print!("└ ");
@@ -54,7 +144,7 @@ fn print_binary_expression(binary_expression: &BinaryExpression, indentation: us
 	print_expression(binary_expression.right_operand(), indentation + 1);
 }
 
-fn print_indentation(indentation: usize) {
+pub fn print_indentation(indentation: usize) {
 	for _ in 0..indentation {
 		print!("  ");
 	}
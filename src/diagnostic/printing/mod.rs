@@ -1,10 +1,33 @@
 mod expression;
+mod statement;
 
-use crate::syntax::Syntax;
-use expression::print_expression;
+use crate::lexing::Token;
+use crate::diagnostic::SourceFile;
+use crate::syntax::{
+	Syntax,
+	Program
+};
+use statement::print_statements;
 
 pub fn print_syntax(syntax: &Syntax) {
 	match syntax {
-		Syntax::Expression(expression) => print_expression(expression, 0)
+		Syntax::Program(program) => print_program(program)
+	}
+}
+
+fn print_program(program: &Program) {
+	println!("program");
+	print_statements(program.statements(), 0);
+}
+
+// Every token on a line of its own, after the trivia that comes before it.
+pub fn print_tokens(tokens: &[Token], file: &SourceFile) {
+	for token in tokens {
+		for trivia in token.trivia() {
+			println!("  {:?} {:?}", trivia.kind(), trivia.text());
+		}
+
+		let (line, column) = file.locate(token.span().start());
+		println!("{:?} {:?} at {}:{}", token.kind(), token.text(), line + 1, column + 1);
 	}
 }
@@ -0,0 +1,137 @@
+use crate::syntax::{
+	Statement,
+	VariableDeclaration,
+	Block,
+	ElseBranch,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	FunctionDeclaration,
+	StructDeclaration,
+	ReturnStatement,
+	TypeAnnotation
+};
+use crate::diagnostic::printing::expression::{
+	print_expression,
+	print_indentation
+};
+
+pub fn print_statement(statement: &Statement, indentation: usize) {
+	match statement {
+		Statement::Expression(expression_statement) => print_expression(expression_statement.expression(), indentation),
+		Statement::VariableDeclaration(variable_declaration) => print_variable_declaration(variable_declaration, indentation),
+		Statement::Block(block) => print_block(block, indentation),
+		Statement::If(if_statement) => print_if_statement(if_statement, indentation),
+		Statement::While(while_statement) => print_while_statement(while_statement, indentation),
+		Statement::For(for_statement) => print_for_statement(for_statement, indentation),
+		Statement::Break => println!("break"),
+		Statement::Continue => println!("continue"),
+		Statement::Function(function_declaration) => print_function_declaration(function_declaration, indentation),
+		Statement::Return(return_statement) => print_return_statement(return_statement, indentation),
+		Statement::Struct(struct_declaration) => print_struct_declaration(struct_declaration, indentation)
+	}
+}
+
+pub fn print_statements(statements: &[Statement], indentation: usize) {
+	for statement in statements {
+		print_indentation(indentation);
+		print!("└ ");
+		print_statement(statement, indentation + 1);
+	}
+}
+
+fn print_variable_declaration(variable_declaration: &VariableDeclaration, indentation: usize) {
+	println!("let {}{}", variable_declaration.name(), format_annotation(": ", variable_declaration.annotation()));
+	print_documentation(variable_declaration.documentation(), indentation);
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(variable_declaration.value(), indentation + 1);
+}
+
+fn print_block(block: &Block, indentation: usize) {
+	println!("block");
+	print_statements(block.statements(), indentation);
+}
+
+fn print_if_statement(if_statement: &IfStatement, indentation: usize) {
+	println!("if");
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(if_statement.condition(), indentation + 1);
+	print_indentation(indentation);
+	print!("└ ");
+	print_block(if_statement.consequence(), indentation + 1);
+
+	if let Some(alternative) = if_statement.alternative() {
+		print_indentation(indentation);
+		println!("└ else");
+		print_indentation(indentation + 1);
+		print!("└ ");
+
+		match alternative {
+			ElseBranch::If(else_if_statement) => print_if_statement(else_if_statement, indentation + 2),
+			ElseBranch::Block(block) => print_block(block, indentation + 2)
+		}
+	}
+}
+
+fn print_while_statement(while_statement: &WhileStatement, indentation: usize) {
+	println!("while");
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(while_statement.condition(), indentation + 1);
+	print_indentation(indentation);
+	print!("└ ");
+	print_block(while_statement.body(), indentation + 1);
+}
+
+fn print_for_statement(for_statement: &ForStatement, indentation: usize) {
+	println!("for {}", for_statement.variable());
+	print_indentation(indentation);
+	print!("└ ");
+	print_expression(for_statement.iterable(), indentation + 1);
+	print_indentation(indentation);
+	print!("└ ");
+	print_block(for_statement.body(), indentation + 1);
+}
+
+fn print_function_declaration(function_declaration: &FunctionDeclaration, indentation: usize) {
+	let parameters = function_declaration.parameters().iter()
+		.map(|parameter| format!("{}{}", parameter.name(), format_annotation(": ", parameter.annotation())))
+		.collect::<Vec<_>>();
+
+	println!("fn {}({}){}", function_declaration.name(), parameters.join(", "), format_annotation(" -> ", function_declaration.result()));
+	print_documentation(function_declaration.documentation(), indentation);
+	print_statements(function_declaration.body().statements(), indentation);
+}
+
+fn print_return_statement(return_statement: &ReturnStatement, indentation: usize) {
+	println!("return");
+
+	if let Some(value) = return_statement.value() {
+		print_indentation(indentation);
+		print!("└ ");
+		print_expression(value, indentation + 1);
+	}
+}
+
+fn print_struct_declaration(struct_declaration: &StructDeclaration, indentation: usize) {
+	println!("struct {}", struct_declaration.name());
+	print_documentation(struct_declaration.documentation(), indentation);
+
+	for field in struct_declaration.fields() {
+		print_indentation(indentation);
+		println!("└ {}{}", field.name(), format_annotation(": ", field.annotation()));
+	}
+}
+
+fn print_documentation(documentation: &[&str], indentation: usize) {
+	for line in documentation {
+		print_indentation(indentation);
+		println!("└ ///{}", line);
+	}
+}
+
+fn format_annotation(introducer: &str, annotation: Option<&TypeAnnotation>) -> String {
+	annotation.map_or(String::new(), |annotation| format!("{}{}", introducer, annotation))
+}
@@ -0,0 +1,241 @@
+use crate::diagnostic::{
+	Diagnostic,
+	Span,
+	FileId,
+	SourceFile,
+	SourceMap
+};
+
+const TAB_WIDTH: usize = 4;
+
+struct Marker<'a> {
+	file: FileId,
+	line: usize,
+	start_column: usize,
+	end_column: usize,
+	character: char,
+	message: &'a str
+}
+
+fn render_diagnostic(diagnostic: &Diagnostic, source_map: &SourceMap) -> String {
+	let primary_span = diagnostic.primary_label().span();
+	let primary_file = source_map.file(primary_span.file());
+	let (primary_line, primary_column) = primary_file.locate(primary_span.start());
+
+	let mut markers = vec![marker(primary_file, primary_span, '^', diagnostic.primary_label().message())];
+
+	for label in diagnostic.secondary_labels() {
+		markers.push(marker(source_map.file(label.span().file()), label.span(), '-', label.message()));
+	}
+
+	// Labels in the file of the primary one come first, those in other files after it, such as earlier inputs of the
+	// interactive session.
+	markers.sort_by_key(|marker| (marker.file != primary_file.id(), marker.file, marker.line, marker.start_column));
+
+	let last_line = markers.iter().map(|marker| marker.line).max().unwrap_or(primary_line);
+	let gutter_width = (last_line + 1).to_string().len();
+	let gutter = " ".repeat(gutter_width);
+
+	let mut output = format!("{}[{}]: {}\n", diagnostic.severity(), diagnostic.code(), diagnostic.message());
+	output += &format!("{}--> {}:{}:{}\n", gutter, primary_file.name(), primary_line + 1, primary_column + 1);
+	output += &format!("{} |\n", gutter);
+
+	let mut previous_file = primary_file.id();
+	let mut previous_line = None;
+
+	for marker in &markers {
+		let file = source_map.file(marker.file);
+		let text = file.line(marker.line);
+
+		if marker.file != previous_file {
+			output += &format!("{} |\n", gutter);
+			output += &format!("{}::: {}:{}:{}\n", gutter, file.name(), marker.line + 1, marker.start_column + 1);
+			output += &format!("{} |\n", gutter);
+			previous_file = marker.file;
+			previous_line = None;
+		}
+
+		if previous_line != Some(marker.line) {
+			if previous_line.is_some_and(|previous_line| previous_line + 1 < marker.line) {
+				output += "...\n";
+			}
+
+			output += &format!("{:>width$} | {}\n", marker.line + 1, expand_tabs(text), width = gutter_width);
+			previous_line = Some(marker.line);
+		}
+
+		let padding = display_width(text, 0, marker.start_column);
+		let underline = display_width(text, marker.start_column, marker.end_column).max(1);
+		let mut marker_line = format!("{} | {}{}", gutter, " ".repeat(padding), marker.character.to_string().repeat(underline));
+
+		if !marker.message.is_empty() {
+			marker_line += " ";
+			marker_line += marker.message;
+		}
+
+		output += &marker_line;
+		output.push('\n');
+	}
+
+	if !diagnostic.notes().is_empty() {
+		output += &format!("{} |\n", gutter);
+
+		for note in diagnostic.notes() {
+			output += &format!("{} = note: {}\n", gutter, note);
+		}
+	}
+
+	output
+}
+
+pub fn print_diagnostic(diagnostic: &Diagnostic, source_map: &SourceMap) {
+	eprintln!("{}", render_diagnostic(diagnostic, source_map));
+}
+
+fn marker<'a>(file: &SourceFile, span: Span, character: char, message: &'a str) -> Marker<'a> {
+	let (line, start_column) = file.locate(span.start());
+	let (end_line, end_column) = file.locate(span.end());
+
+	// Spans running past the end of their first line are underlined up to the end of it.
+	let end_column = if end_line == line {
+		end_column
+	} else {
+		file.line(line).chars().count()
+	};
+
+	Marker {
+		file: file.id(),
+		line,
+		start_column,
+		end_column,
+		character,
+		message
+	}
+}
+
+fn character_width(character: char) -> usize {
+	if character == '\t' {
+		TAB_WIDTH
+	} else {
+		1
+	}
+}
+
+// Width on screen of the characters between two columns of a line, past its end every column counts as one.
+fn display_width(text: &str, start_column: usize, end_column: usize) -> usize {
+	let line_width = text.chars().count();
+
+	text.chars()
+		.skip(start_column)
+		.take(end_column.saturating_sub(start_column))
+		.map(character_width)
+		.sum::<usize>() + end_column.max(line_width).saturating_sub(start_column.max(line_width))
+}
+
+fn expand_tabs(text: &str) -> String {
+	text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The span of the first occurrence of `text` in the file.
+	fn find(source_map: &SourceMap, file: FileId, text: &str) -> Span {
+		let start = source_map.file(file).text().find(text).unwrap_or_else(|| panic!("`{}` is not in the file", text));
+
+		Span::new(file, start, start + text.len())
+	}
+
+	// Renders an error labelled `here` at `primary` in a file holding `code`.
+	fn render(code: &str, primary: &str) -> String {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", code);
+
+		render_diagnostic(&Diagnostic::error("E0000", "message", find(&source_map, file, primary)).with_primary_label("here"), &source_map)
+	}
+
+	#[test]
+	fn carets_count_characters_rather_than_bytes() {
+		assert_eq!(render("let é = \"ü\" + 1;", "+"), [
+			"error[E0000]: message",
+			" --> <test>:1:13",
+			"  |",
+			"1 | let é = \"ü\" + 1;",
+			"  |             ^ here",
+			""
+		].join("\n"));
+	}
+
+	#[test]
+	fn tabs_are_expanded_in_the_line_and_under_it() {
+		assert_eq!(render("\tlet a = 1 +\t\"a\";", "\"a\""), [
+			"error[E0000]: message",
+			" --> <test>:1:14",
+			"  |",
+			"1 |     let a = 1 +    \"a\";",
+			"  |                    ^^^ here",
+			""
+		].join("\n"));
+	}
+
+	#[test]
+	fn spans_past_the_end_of_their_line_are_underlined_to_its_end() {
+		assert_eq!(render("let a = (1\n+ 2);", "(1\n+ 2)"), [
+			"error[E0000]: message",
+			" --> <test>:1:9",
+			"  |",
+			"1 | let a = (1",
+			"  |         ^^ here",
+			""
+		].join("\n"));
+	}
+
+	#[test]
+	fn lines_between_distant_labels_are_left_out() {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", "let a = 1;\nlet b = 2;\n\n\na + c");
+		let diagnostic = Diagnostic::error("E0000", "message", find(&source_map, file, "c"))
+			.with_primary_label("here")
+			.with_label(find(&source_map, file, "a"), "first")
+			.with_label(find(&source_map, file, "b"), "second");
+
+		assert_eq!(render_diagnostic(&diagnostic, &source_map), [
+			"error[E0000]: message",
+			" --> <test>:5:5",
+			"  |",
+			"1 | let a = 1;",
+			"  |     - first",
+			"2 | let b = 2;",
+			"  |     - second",
+			"...",
+			"5 | a + c",
+			"  |     ^ here",
+			""
+		].join("\n"));
+	}
+
+	#[test]
+	fn labels_in_other_files_come_under_a_header_of_their_own() {
+		let mut source_map = SourceMap::new();
+		let first = source_map.add("<repl:1>", "let a = 1;");
+		let second = source_map.add("<repl:2>", "a = \"a\"");
+		let diagnostic = Diagnostic::error("E0000", "message", find(&source_map, second, "\"a\""))
+			.with_primary_label("here")
+			.with_label(find(&source_map, first, "1"), "there");
+
+		assert_eq!(render_diagnostic(&diagnostic, &source_map), [
+			"error[E0000]: message",
+			" --> <repl:2>:1:5",
+			"  |",
+			"1 | a = \"a\"",
+			"  |     ^^^ here",
+			"  |",
+			" ::: <repl:1>:1:9",
+			"  |",
+			"1 | let a = 1;",
+			"  |         - there",
+			""
+		].join("\n"));
+	}
+}
@@ -0,0 +1,111 @@
+// Identifies one of the files added to a `SourceMap`, ordered by when they were added.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct FileId(usize);
+
+pub struct SourceFile<'a> {
+	id: FileId,
+	name: String,
+	text: &'a str,
+	// Byte index every line starts at, to find the line of an index without going over the text again.
+	line_starts: Vec<usize>
+}
+
+impl<'a> SourceFile<'a> {
+	fn new(id: FileId, name: String, text: &'a str) -> Self {
+		let line_starts = [0].into_iter()
+			.chain(text.match_indices('\n').map(|(index, _)| index + 1))
+			.collect();
+
+		Self {
+			id,
+			name,
+			text,
+			line_starts
+		}
+	}
+
+	pub fn id(&self) -> FileId {
+		self.id
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn text(&self) -> &'a str {
+		self.text
+	}
+
+	// Zero based line number and character column of a byte index.
+	pub fn locate(&self, index: usize) -> (usize, usize) {
+		let line = match self.line_starts.binary_search(&index) {
+			Ok(line) => line,
+			Err(line) => line - 1
+		};
+
+		let text = self.line(line);
+		let offset = index - self.line_starts[line];
+
+		// Offsets past the text, such as a stripped `\r`, count one column per byte.
+		let column = match text.get(..offset) {
+			Some(before) => before.chars().count(),
+			None => text.chars().count() + offset.saturating_sub(text.len())
+		};
+
+		(line, column)
+	}
+
+	// The text of a zero based line, without its line break.
+	pub fn line(&self, line: usize) -> &'a str {
+		let start = self.line_starts[line];
+		let end = self.line_starts.get(line + 1).map_or(self.text.len(), |next_start| next_start - 1);
+		let text = &self.text[start..end];
+
+		text.strip_suffix('\r').unwrap_or(text)
+	}
+}
+
+// Every file, or input of the interactive session, that code was read from. Spans refer to them by their id.
+pub struct SourceMap<'a> {
+	files: Vec<SourceFile<'a>>
+}
+
+impl<'a> SourceMap<'a> {
+	pub fn new() -> Self {
+		Self {
+			files: Vec::new()
+		}
+	}
+
+	pub fn add(&mut self, name: impl Into<String>, text: &'a str) -> FileId {
+		let id = FileId(self.files.len());
+		self.files.push(SourceFile::new(id, name.into(), text));
+
+		id
+	}
+
+	pub fn file(&self, id: FileId) -> &SourceFile<'a> {
+		&self.files[id.0]
+	}
+
+	pub fn files(&self) -> &[SourceFile<'a>] {
+		&self.files
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn indices_are_located_by_line_and_character() {
+		let mut source_map = SourceMap::new();
+		let first = source_map.add("first", "a\n\u{E9}t\u{E9} b\r\n\nc");
+		let second = source_map.add("second", "");
+		let file = source_map.file(first);
+
+		assert_eq!([0, 1, 2, 7, 9, 10, 11, 12].map(|index| file.locate(index)), [(0, 0), (0, 1), (1, 0), (1, 3), (1, 5), (1, 6), (2, 0), (3, 0)]);
+		assert_eq!([0, 1, 2, 3].map(|line| file.line(line)), ["a", "\u{E9}t\u{E9} b", "", "c"]);
+		assert_eq!((source_map.file(second).name(), source_map.file(second).locate(0)), ("second", (0, 0)));
+	}
+}
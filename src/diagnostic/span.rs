@@ -0,0 +1,36 @@
+use crate::diagnostic::FileId;
+
+// The bytes from `start` up to `end` of a file.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Span {
+	file: FileId,
+	start: usize,
+	end: usize
+}
+
+impl Span {
+	pub fn new(file: FileId, start: usize, end: usize) -> Self {
+		Self {
+			file,
+			start,
+			end
+		}
+	}
+
+	pub fn file(&self) -> FileId {
+		self.file
+	}
+
+	pub fn start(&self) -> usize {
+		self.start
+	}
+
+	pub fn end(&self) -> usize {
+		self.end
+	}
+
+	// The smallest span covering both `self` and `other`, which are in the same file.
+	pub fn to(&self, other: Span) -> Span {
+		Span::new(self.file, self.start.min(other.start), self.end.max(other.end))
+	}
+}
@@ -1,36 +1,102 @@
-use std::cell::Cell;
+use std::cell::{
+	Cell,
+	RefCell
+};
 use crate::lexing::{
 	Token,
-	TokenKind
+	TokenKind,
+	TokenValue,
+	Trivia,
+	TriviaKind,
+	KEYWORDS
+};
+use crate::diagnostic::{
+	Diagnostic,
+	Span,
+	FileId,
+	SourceFile
 };
 
+// The suffixes an integer literal can take, with the largest value each allows.
+// They only check the literal, which is an `Integer` like any other.
+const INTEGER_SUFFIXES: [(&str, i64); 7] = [
+	("i8", i8::MAX as i64),
+	("i16", i16::MAX as i64),
+	("i32", i32::MAX as i64),
+	("i64", i64::MAX),
+	("u8", u8::MAX as i64),
+	("u16", u16::MAX as i64),
+	("u32", u32::MAX as i64)
+];
+
+// Any decimal literal can take these, which make it a `Float` of full precision with at most the largest value each allows.
+const FLOAT_SUFFIXES: [(&str, f64); 2] = [
+	("f32", f32::MAX as f64),
+	("f64", f64::MAX)
+];
+
+// Walks through the text by byte index, a character at a time. As an iterator it gives every token up to and
+// including the `End` one.
 pub struct Lexer<'a> {
 	index: Cell<usize>,
-	text: &'a str
+	// Where the token being lexed starts.
+	token_start: Cell<usize>,
+	file: FileId,
+	text: &'a str,
+	// For every `${` being lexed the index its string starts at and how many braces are open inside it,
+	// the `}` closing the innermost one continues its string.
+	interpolations: RefCell<Vec<(usize, usize)>>,
+	// The lines of the `///` comments before the token being lexed, they are attached to it.
+	documentation: RefCell<Vec<&'a str>>,
+	// Whether white space and comments are kept on the tokens, so that their text adds up to the whole source.
+	keeps_trivia: bool,
+	trivia: RefCell<Vec<Trivia<'a>>>,
+	ended: bool
 }
 
 impl<'a> Lexer<'a> {
-	pub fn new(text: &'a str) -> Self {
+	pub fn new(file: &SourceFile<'a>) -> Self {
 		Self {
 			index: Cell::new(0),
-			text
+			token_start: Cell::new(0),
+			file: file.id(),
+			text: file.text(),
+			interpolations: RefCell::new(Vec::new()),
+			documentation: RefCell::new(Vec::new()),
+			keeps_trivia: false,
+			trivia: RefCell::new(Vec::new()),
+			ended: false
 		}
 	}
 
-	pub fn lex(&self) -> Result<Token<'a>, &'static str> {
-		if let Some(end_token) = self.lex_white_space() {
-			return Ok(end_token);
+	pub fn with_trivia(mut self) -> Self {
+		self.keeps_trivia = true;
+		self
+	}
+
+	pub fn lex(&self) -> Result<Token<'a>, Diagnostic> {
+		self.documentation.borrow_mut().clear();
+		self.trivia.borrow_mut().clear();
+		self.skip_trivia()?;
+		self.token_start.set(self.index.get());
+
+		if self.is_at_end() {
+			return Ok(self.token(TokenKind::End));
 		}
 
-		if let Some(number_literal_token) = self.lex_number_literal() {
+		if let Some(number_literal_token) = self.lex_number_literal()? {
 			return Ok(number_literal_token);
 		}
 
-		if let Some(string_literal_token) = self.lex_string_literal() {
+		if let Some(interpolation_token) = self.lex_interpolation_end()? {
+			return Ok(interpolation_token);
+		}
+
+		if let Some(string_literal_token) = self.lex_string_literal()? {
 			return Ok(string_literal_token);
 		}
 
-		if let Some(character_literal_token) = self.lex_character_literal() {
+		if let Some(character_literal_token) = self.lex_character_literal()? {
 			return Ok(character_literal_token);
 		}
 
@@ -39,59 +105,184 @@ impl<'a> Lexer<'a> {
 		}
 
 		if let Some(operator_token) = self.lex_operator() {
+			if let Some((_, depth)) = self.interpolations.borrow_mut().last_mut() {
+				match operator_token.kind() {
+					TokenKind::OpenBrace => *depth += 1,
+					TokenKind::CloseBrace => *depth -= 1,
+					_ => ()
+				}
+			}
+
 			return Ok(operator_token);
 		}
 
-		Err("Unknown token.")
+		// Skip the offending character so lexing can resume after it.
+		let current_index = self.index.get();
+		let current_character = self.current_character();
+		self.increment_index();
+
+		Err(
+			Diagnostic::error("E0001", format!("unknown character `{}`", current_character), self.span(current_index, self.index.get()))
+				.with_primary_label("not recognised by kalf")
+		)
 	}
 
+	// Moves past the current character.
 	fn increment_index(&self) {
-		self.index.set(self.index.get() + 1);
+		if !self.is_at_end() {
+			self.index.set(self.index.get() + self.current_character().len_utf8());
+		}
 	}
 
 	fn increment_index_by(&self, by: usize) {
-		self.index.set(self.index.get() + by);
+		for _ in 0..by {
+			self.increment_index();
+		}
+	}
+
+	// The token from where the current one started up to the current character.
+	fn token(&self, kind: TokenKind) -> Token<'a> {
+		let start = self.token_start.get();
+
+		Token::new(self.span(start, self.index.get()), &self.text[start..self.index.get()], kind)
+			.with_documentation(self.documentation.take())
+			.with_trivia(self.trivia.take())
+	}
+
+	fn span(&self, start: usize, end: usize) -> Span {
+		Span::new(self.file, start, end)
+	}
+
+	fn is_at_end(&self) -> bool {
+		self.text.len() <= self.index.get()
 	}
 
 	fn current_character(&self) -> char {
-		self.text.chars().nth(self.index.get()).unwrap()
+		self.current_character_offset(0)
 	}
 
+	// Past the end of the text this is `\0`, which never continues an operator.
 	fn current_character_offset(&self, offset: usize) -> char {
-		self.text.chars().nth(self.index.get() + offset).unwrap()
+		self.text[self.index.get()..].chars().nth(offset).unwrap_or('\0')
 	}
 
-	fn lex_white_space(&self) -> Option<Token<'a>> {
+	// Skips white space and comments up to the next token.
+	fn skip_trivia(&self) -> Result<(), Diagnostic> {
 		loop {
-			match self.text.chars().nth(self.index.get()) {
-				Some(current_character) => {
-					if current_character.is_whitespace() {
+			let start_position = self.index.get();
+
+			let trivia_kind = match (self.current_character(), self.current_character_offset(1)) {
+				('/', '/') => self.skip_line_comment(),
+				('/', '*') => {
+					self.skip_block_comment()?;
+					TriviaKind::BlockComment
+				},
+				('\n', _) => {
+					self.increment_index();
+					TriviaKind::Newline
+				},
+				(current_character, _) if !self.is_at_end() && current_character.is_whitespace() => {
+					while !self.is_at_end() && self.current_character().is_whitespace() && self.current_character() != '\n' {
 						self.increment_index();
-					} else {
-						return None;
 					}
+
+					TriviaKind::WhiteSpace
 				},
-				None => {
-					return Some(Token::new(self.text.len(), "\0", TokenKind::End));
-				}
+				_ => return Ok(())
+			};
+
+			if self.keeps_trivia {
+				self.trivia.borrow_mut().push(Trivia::new(trivia_kind, &self.text[start_position..self.index.get()]));
+			}
+		}
+	}
+
+	fn skip_line_comment(&self) -> TriviaKind {
+		let start_position = self.index.get();
+
+		while !self.is_at_end() && self.current_character() != '\n' {
+			self.increment_index();
+		}
+
+		let comment = &self.text[start_position..self.index.get()];
+
+		// As in Rust `////` starts a regular comment again.
+		if let Some(line) = comment.strip_prefix("///").filter(|line| !line.starts_with('/')) {
+			self.documentation.borrow_mut().push(line.strip_suffix('\r').unwrap_or(line));
+			TriviaKind::DocComment
+		} else {
+			TriviaKind::LineComment
+		}
+	}
+
+	// Block comments nest, `/* a /* b */ c */` is a single comment.
+	fn skip_block_comment(&self) -> Result<(), Diagnostic> {
+		// Where the comments that are still open start.
+		let mut openings = Vec::new();
+
+		loop {
+			match (self.current_character(), self.current_character_offset(1)) {
+				('/', '*') => {
+					openings.push(self.index.get());
+					self.increment_index_by(2);
+				},
+				('*', '/') => {
+					openings.pop();
+					self.increment_index_by(2);
+
+					if openings.is_empty() {
+						return Ok(());
+					}
+				},
+				_ if self.is_at_end() => {
+					let start_position = openings.last().copied().unwrap_or(self.index.get());
+
+					return Err(
+						Diagnostic::error("E0008", "unterminated block comment", self.span(start_position, start_position + 2))
+							.with_primary_label("comment starts here")
+							.with_note("block comments nest, every `/*` needs its own `*/`")
+					);
+				},
+				_ => self.increment_index()
 			}
 		}
 	}
 
 	fn lex_operator(&self) -> Option<Token<'a>> {
-		let current_index = self.index.get();
 		let arithmetic_operator_token_kind = match self.current_character() {
 			'+' => Some(TokenKind::PlusOperator),
-			'-' => Some(TokenKind::MinusOperator),
+			'-' => {
+				if self.current_character_offset(1) == '>' {
+					self.increment_index_by(2);
+					return Some(self.token(TokenKind::Arrow));
+				} else {
+					Some(TokenKind::MinusOperator)
+				}
+			},
 			'*' => Some(TokenKind::StarOperator),
 			'/' => Some(TokenKind::SlashOperator),
 			'%' => Some(TokenKind::PercentageOperator),
 			'(' => Some(TokenKind::OpenParenthesis),
 			')' => Some(TokenKind::CloseParenthesis),
+			'.' => {
+				if self.current_character_offset(1) == '.' {
+					self.increment_index_by(2);
+					return Some(self.token(TokenKind::RangeOperator));
+				} else {
+					Some(TokenKind::Dot)
+				}
+			},
+			'{' => Some(TokenKind::OpenBrace),
+			'}' => Some(TokenKind::CloseBrace),
+			'[' => Some(TokenKind::OpenBracket),
+			']' => Some(TokenKind::CloseBracket),
+			';' => Some(TokenKind::Semicolon),
+			',' => Some(TokenKind::Comma),
+			':' => Some(TokenKind::Colon),
 			'!' => {
 				if self.current_character_offset(1) == '=' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::NotEqualityOperator));
+					return Some(self.token(TokenKind::NotEqualityOperator));
 				} else {
 					Some(TokenKind::LogicalNotOperator)
 				}
@@ -99,7 +290,7 @@ impl<'a> Lexer<'a> {
 			'|' => {
 				if self.current_character_offset(1) == '|' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::LogicalOrOperator));
+					return Some(self.token(TokenKind::LogicalOrOperator));
 				} else {
 					None
 				}
@@ -107,7 +298,7 @@ impl<'a> Lexer<'a> {
 			'&' => {
 				if self.current_character_offset(1) == '&' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::LogicalAndOperator));
+					return Some(self.token(TokenKind::LogicalAndOperator));
 				} else {
 					None
 				}
@@ -115,7 +306,7 @@ impl<'a> Lexer<'a> {
 			'<' => {
 				if self.current_character_offset(1) == '=' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::LessThanEqualToOperator));
+					return Some(self.token(TokenKind::LessThanEqualToOperator));
 				} else {
 					Some(TokenKind::LessThanOperator)
 				}
@@ -123,7 +314,7 @@ impl<'a> Lexer<'a> {
 			'>' => {
 				if self.current_character_offset(1) == '=' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::GreaterThanEqualToOperator));
+					return Some(self.token(TokenKind::GreaterThanEqualToOperator));
 				} else {
 					Some(TokenKind::GreaterThanOperator)
 				}
@@ -131,7 +322,7 @@ impl<'a> Lexer<'a> {
 			'=' => {
 				if self.current_character_offset(1) == '=' {
 					self.increment_index_by(2);
-					return Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::EqualityOperator));
+					return Some(self.token(TokenKind::EqualityOperator));
 				} else {
 					Some(TokenKind::AssignmentOperator)
 				}
@@ -141,91 +332,626 @@ impl<'a> Lexer<'a> {
 
 		if let Some(arithmetic_operator_token_kind) = arithmetic_operator_token_kind {
 			self.increment_index();
-			Some(Token::new(current_index, &self.text[current_index..self.index.get()], arithmetic_operator_token_kind))
+			Some(self.token(arithmetic_operator_token_kind))
 		} else {
 			None
 		}
 	}
 
-	fn lex_number_literal(&self) -> Option<Token<'a>> {
-		if self.current_character().is_numeric() {
-			let start_position = self.index.get();
-			let mut number_literal_kind = TokenKind::IntegerLiteral;
+	// Such as `42`, `1_000`, `0xFF`, `0b1010`, `0o17`, `6.02e23` or with a suffix as in `10i32` and `2.5f32`.
+	fn lex_number_literal(&self) -> Result<Option<Token<'a>>, Diagnostic> {
+		if !self.current_character().is_ascii_digit() {
+			return Ok(None);
+		}
 
-			loop {
-				let current_character = self.current_character();
+		let start_position = self.index.get();
 
-				if current_character == '.' {
-					if number_literal_kind == TokenKind::FloatingPointLiteral {
-						return None;
-					} else {
-						number_literal_kind = TokenKind::FloatingPointLiteral;
-					}
-				} else if !current_character.is_numeric() {
-					break;
-				}
+		let (radix, base) = match (self.current_character(), self.current_character_offset(1)) {
+			('0', 'x') => (16, "hexadecimal"),
+			('0', 'o') => (8, "octal"),
+			('0', 'b') => (2, "binary"),
+			_ => (10, "decimal")
+		};
 
+		if radix != 10 {
+			self.increment_index_by(2);
+		}
+
+		let digits_start = self.index.get();
+		let mut is_float = false;
+		self.skip_digits(radix);
+
+		if radix == 10 {
+			// `1.5` goes on, but the `.` in `0..10` and `1.len()` is not part of the literal.
+			if self.current_character() == '.' && self.current_character_offset(1).is_ascii_digit() {
+				is_float = true;
+				self.increment_index();
+				self.skip_digits(10);
+			}
+
+			if matches!(self.current_character(), 'e' | 'E') {
+				is_float = true;
+				let exponent_start = self.index.get();
 				self.increment_index();
 
-				if self.text.len() <= self.index.get() {
-					break;
+				if matches!(self.current_character(), '+' | '-') {
+					self.increment_index();
+				}
+
+				if !self.skip_digits(10) {
+					return Err(
+						Diagnostic::error("E0010", "exponent has no digits", self.span(exponent_start, self.index.get()))
+							.with_primary_label("expected digits after this")
+							.with_note("write exponents as in `6.02e23` or `1e-9`")
+					);
 				}
 			}
+		}
 
-			Some(Token::new(start_position, &self.text[start_position..self.index.get()], number_literal_kind))
-		} else {
-			None
+		let digits = self.text[digits_start..self.index.get()].replace('_', "");
+		let suffix_start = self.index.get();
+
+		while self.current_character().is_alphanumeric() || self.current_character() == '_' {
+			self.increment_index();
+		}
+
+		let suffix = &self.text[suffix_start..self.index.get()];
+		let span = self.span(start_position, self.index.get());
+
+		if suffix.starts_with(|character: char| character.is_ascii_digit()) {
+			return Err(
+				Diagnostic::error("E0009", format!("invalid digit `{}` in {} literal", &suffix[..1], base), self.span(suffix_start, suffix_start + 1))
+					.with_primary_label(format!("not a base {} digit", radix))
+			);
+		}
+
+		if digits.is_empty() {
+			return Err(
+				Diagnostic::error("E0010", format!("{} literal has no digits", base), span)
+					.with_primary_label(format!("expected digits after `{}`", &self.text[start_position..digits_start]))
+			);
+		}
+
+		let float_suffix = FLOAT_SUFFIXES.iter().find(|(name, _)| *name == suffix);
+		let is_float_suffix = float_suffix.is_some();
+		let integer_suffix = INTEGER_SUFFIXES.iter().find(|(name, _)| *name == suffix);
+
+		let is_valid_suffix = suffix.is_empty() || (is_float_suffix && radix == 10) || (integer_suffix.is_some() && !is_float);
+
+		if !is_valid_suffix {
+			let valid_suffixes = match (is_float, radix) {
+				(true, _) => "`f32` and `f64`",
+				(false, 10) => "`i8`, `i16`, `i32`, `i64`, `u8`, `u16`, `u32`, `f32` and `f64`",
+				(false, _) => "`i8`, `i16`, `i32`, `i64`, `u8`, `u16` and `u32`"
+			};
+
+			return Err(
+				Diagnostic::error("E0011", format!("invalid suffix `{}` for {} literal", suffix, if is_float { "floating point" } else { base }), self.span(suffix_start, self.index.get()))
+					.with_primary_label("unknown suffix")
+					.with_note(format!("the valid suffixes here are {}", valid_suffixes))
+			);
+		}
+
+		if is_float || is_float_suffix {
+			let (type_name, largest) = float_suffix.map_or(("Float", f64::MAX), |&(name, largest)| (name, largest));
+
+			match digits.parse::<f64>().ok().filter(|value| *value <= largest) {
+				Some(value) => return Ok(Some(self.token(TokenKind::FloatingPointLiteral).with_value(TokenValue::Float(value)))),
+				None => return Err(
+					Diagnostic::error("E0012", "floating point literal is too large", span)
+						.with_primary_label(format!("the largest `{}` is {:e}", type_name, largest))
+				)
+			}
+		}
+
+		let (type_name, largest) = integer_suffix.map_or(("Integer", i64::MAX), |&(name, largest)| (name, largest));
+
+		match i64::from_str_radix(&digits, radix).ok().filter(|value| *value <= largest) {
+			Some(value) => Ok(Some(self.token(TokenKind::IntegerLiteral).with_value(TokenValue::Integer(value)))),
+			None => {
+				let diagnostic = Diagnostic::error("E0012", "integer literal is too large", span)
+					.with_primary_label(format!("the largest `{}` is {}", type_name, largest));
+
+				Err(if suffix.is_empty() { diagnostic.with_note("use a floating point literal such as `1.0` for larger numbers") } else { diagnostic })
+			}
 		}
 	}
 
-	fn lex_string_literal(&self) -> Option<Token<'a>> {
-		if self.current_character() == '"' {
-			let start_position = self.index.get();
+	// Skips the digits of a number in the given radix and the `_`s between them, telling whether there were any digits.
+	fn skip_digits(&self, radix: u32) -> bool {
+		let mut skipped_digits = false;
+
+		while self.current_character().is_digit(radix) || self.current_character() == '_' {
+			skipped_digits |= self.current_character() != '_';
 			self.increment_index();
+		}
 
-			while self.current_character() != '"' {
-				self.increment_index();
+		skipped_digits
+	}
+
+	fn lex_string_literal(&self) -> Result<Option<Token<'a>>, Diagnostic> {
+		if let Some(raw_string_literal_token) = self.lex_raw_string_literal()? {
+			return Ok(Some(raw_string_literal_token));
+		}
+
+		if self.current_character() != '"' {
+			return Ok(None);
+		}
+
+		let start_position = self.index.get();
+		self.increment_index();
+
+		self.lex_string_content(start_position, start_position).map(Some)
+	}
+
+	// The `}` closing an interpolation, rather than a block inside it, goes on with the string.
+	fn lex_interpolation_end(&self) -> Result<Option<Token<'a>>, Diagnostic> {
+		if self.current_character() != '}' {
+			return Ok(None);
+		}
+
+		let string_start = match self.interpolations.borrow().last() {
+			Some(&(string_start, 0)) => string_start,
+			_ => return Ok(None)
+		};
 
-				if self.text.len() <= self.index.get() {
-					return None;
+		self.interpolations.borrow_mut().pop();
+
+		let start_position = self.index.get();
+		self.increment_index();
+
+		self.lex_string_content(start_position, string_start).map(Some)
+	}
+
+	// Lexes a string from after its `"`, or from the `}` of an interpolation in it, up to its closing `"` or the next `${`.
+	// A string with interpolations is split into a token for every piece of text, with the tokens of the expressions in between.
+	// Escape sequences are decoded into the token's value. The rest of a piece with an invalid escape is still
+	// skipped, so lexing resumes after it.
+	fn lex_string_content(&self, start_position: usize, string_start: usize) -> Result<Token<'a>, Diagnostic> {
+		let mut value = String::new();
+		let mut invalid_escape = None;
+
+		let interpolated = loop {
+			if self.is_at_end() {
+				return Err(unterminated_string_literal(self.span(string_start, string_start + 1), "`\"`"));
+			}
+
+			match self.current_character() {
+				'"' => {
+					self.increment_index();
+					break false;
+				},
+				'$' if self.current_character_offset(1) == '{' => {
+					self.increment_index_by(2);
+					break true;
+				},
+				'\\' => match self.lex_escape() {
+					Ok(Some(character)) => value.push(character),
+					Ok(None) => (),
+					Err(diagnostic) => {
+						invalid_escape.get_or_insert(diagnostic);
+					}
+				},
+				character => {
+					value.push(character);
+					self.increment_index();
 				}
 			}
+		};
+
+		if interpolated {
+			self.interpolations.borrow_mut().push((string_start, 0));
+		}
+
+		if let Some(diagnostic) = invalid_escape {
+			return Err(diagnostic);
+		}
+
+		let kind = match (start_position == string_start, interpolated) {
+			(true, false) => TokenKind::StringLiteral,
+			(true, true) => TokenKind::InterpolationStart,
+			(false, true) => TokenKind::InterpolationMiddle,
+			(false, false) => TokenKind::InterpolationEnd
+		};
+
+		let token = self.token(kind);
+
+		Ok(token.with_value(TokenValue::String(value)))
+	}
+
+	// `r"..."` takes its content as it is written. Any number of `#` can go around the quotes,
+	// the string then only ends at a `"` followed by as many of them, as in `r#"say "hi""#`.
+	fn lex_raw_string_literal(&self) -> Result<Option<Token<'a>>, Diagnostic> {
+		if self.current_character() != 'r' {
+			return Ok(None);
+		}
+
+		let hashes = (1..).take_while(|&offset| self.current_character_offset(offset) == '#').count();
+
+		if self.current_character_offset(hashes + 1) != '"' {
+			return Ok(None);
+		}
+
+		let start_position = self.index.get();
+		self.increment_index_by(hashes + 2);
+
+		let content_start = self.index.get();
+
+		loop {
+			if self.is_at_end() {
+				return Err(unterminated_string_literal(self.span(start_position, start_position + 1), &format!("`\"{}`", "#".repeat(hashes))));
+			}
+
+			if self.current_character() == '"' && (1..=hashes).all(|offset| self.current_character_offset(offset) == '#') {
+				break;
+			}
 
 			self.increment_index();
+		}
 
-			Some(Token::new(start_position, &self.text[start_position..self.index.get()], TokenKind::StringLiteral))
-		} else {
-			None
+		let value = self.text[content_start..self.index.get()].to_string();
+		self.increment_index_by(hashes + 1);
+
+		let token = self.token(TokenKind::StringLiteral);
+
+		Ok(Some(token.with_value(TokenValue::String(value))))
+	}
+
+	// Decodes the escape sequence at the current `\`, moving past it.
+	// A `\` at the end of a line joins the next one on, leaving out the indentation there.
+	fn lex_escape(&self) -> Result<Option<char>, Diagnostic> {
+		let start_position = self.index.get();
+		self.increment_index();
+
+		// Left for the caller to report as unterminated.
+		if self.is_at_end() {
+			return Ok(None);
 		}
+
+		let escaped = self.current_character();
+		self.increment_index();
+
+		Ok(Some(match escaped {
+			'n' => '\n',
+			't' => '\t',
+			'r' => '\r',
+			'0' => '\0',
+			'\\' => '\\',
+			'"' => '"',
+			'\'' => '\'',
+			'$' => '$',
+			'u' => return self.lex_unicode_escape(start_position).map(Some),
+			'\n' | '\r' => {
+				while !self.is_at_end() && self.current_character().is_whitespace() {
+					self.increment_index();
+				}
+
+				return Ok(None);
+			},
+			_ => return Err(
+				Diagnostic::error("E0003", format!("unknown escape sequence `\\{}`", escaped), self.span(start_position, self.index.get()))
+					.with_primary_label("not a valid escape")
+					.with_note("the escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\\"`, `\\'`, `\\$` and `\\u{...}`")
+			)
+		}))
 	}
 
-	fn lex_character_literal(&self) -> Option<Token<'a>> {
-		if self.current_character() == '\'' && self.current_character_offset(2) == '\'' {
-			let current_index = self.index.get();
-			self.increment_index_by(3);
+	// The `{...}` after `\u`, holding one to six hexadecimal digits.
+	fn lex_unicode_escape(&self, start_position: usize) -> Result<char, Diagnostic> {
+		let invalid_escape = |label: String| {
+			Diagnostic::error("E0004", "invalid unicode escape", self.span(start_position, self.index.get()))
+				.with_primary_label(label)
+				.with_note("unicode escapes look like `\\u{1F600}`")
+		};
 
-			Some(Token::new(current_index, &self.text[current_index..self.index.get()], TokenKind::CharacterLiteral))
-		} else {
-			None
+		if self.current_character_offset(0) != '{' {
+			return Err(invalid_escape(String::from("expected `{` after `\\u`")));
+		}
+
+		self.increment_index();
+
+		let mut digits = String::new();
+
+		while self.current_character_offset(0).is_ascii_hexdigit() {
+			digits.push(self.current_character());
+			self.increment_index();
+		}
+
+		if self.current_character_offset(0) != '}' {
+			return Err(invalid_escape(String::from("expected `}` to close the escape")));
+		}
+
+		self.increment_index();
+
+		if digits.is_empty() || digits.len() > 6 {
+			return Err(invalid_escape(String::from("expected one to six hexadecimal digits")));
+		}
+
+		u32::from_str_radix(&digits, 16).ok()
+			.and_then(char::from_u32)
+			.ok_or_else(|| invalid_escape(format!("`{}` is not a Unicode character", digits)))
+	}
+
+	// One character or escape between `'`, decoded like the characters of strings.
+	// A literal left open is only looked for up to the end of its line.
+	fn lex_character_literal(&self) -> Result<Option<Token<'a>>, Diagnostic> {
+		if self.current_character() != '\'' {
+			return Ok(None);
+		}
+
+		let start_position = self.index.get();
+		self.increment_index();
+
+		let mut characters = Vec::new();
+		let mut invalid_escape = None;
+
+		loop {
+			if self.is_at_end() || self.current_character() == '\n' {
+				return Err(
+					Diagnostic::error("E0005", "unterminated character literal", self.span(start_position, start_position + 1))
+						.with_primary_label("character starts here")
+						.with_note("characters are closed with `'`")
+				);
+			}
+
+			match self.current_character() {
+				'\'' => break,
+				'\\' => match self.lex_escape() {
+					Ok(Some(character)) => characters.push(character),
+					Ok(None) => (),
+					Err(diagnostic) => {
+						invalid_escape.get_or_insert(diagnostic);
+					}
+				},
+				character => {
+					characters.push(character);
+					self.increment_index();
+				}
+			}
+		}
+
+		self.increment_index();
+
+		if let Some(diagnostic) = invalid_escape {
+			return Err(diagnostic);
+		}
+
+		let span = self.span(start_position, self.index.get());
+
+		match characters.as_slice() {
+			[character] => Ok(Some(self.token(TokenKind::CharacterLiteral).with_value(TokenValue::Character(*character)))),
+			[] => Err(
+				Diagnostic::error("E0006", "empty character literal", span)
+					.with_primary_label("expected a character")
+					.with_note("write `'\\''` for a quote")
+			),
+			_ => Err(
+				Diagnostic::error("E0007", "character literal holds more than one character", span)
+					.with_primary_label(format!("{} characters", characters.len()))
+					.with_note(format!("use a string for more than one: {:?}", characters.iter().collect::<String>()))
+			)
 		}
 	}
 
 	fn lex_keyword_and_identifier(&self) -> Option<Token<'a>> {
-		if self.current_character().is_alphabetic() {
+		if self.current_character().is_alphabetic() || self.current_character() == '_' {
 			let start_position = self.index.get();
 			self.increment_index();
 
-			while self.current_character().is_alphanumeric() {
+			while !self.is_at_end() && (self.current_character().is_alphanumeric() || self.current_character() == '_') {
 				self.increment_index();
-
-				if self.text.len() <= self.index.get() {
-					break;
-				}
 			}
 
-			Some(Token::new(start_position, &self.text[start_position..self.index.get()], TokenKind::Identifier))
+			let text = &self.text[start_position..self.index.get()];
+			let kind = KEYWORDS.iter()
+				.find(|(keyword, _)| *keyword == text)
+				.map_or(TokenKind::Identifier, |(_, kind)| *kind);
+
+			Some(self.token(kind))
 		} else {
 			None
 		}
 	}
 }
+
+impl<'a> Iterator for Lexer<'a> {
+	type Item = Result<Token<'a>, Diagnostic>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.ended {
+			return None;
+		}
+
+		let result = self.lex();
+		self.ended = result.as_ref().is_ok_and(|token| token.kind() == TokenKind::End);
+
+		Some(result)
+	}
+}
+
+fn unterminated_string_literal(span: Span, closing: &str) -> Diagnostic {
+	Diagnostic::error("E0002", "unterminated string literal", span)
+		.with_primary_label("string starts here")
+		.with_note(format!("strings are closed with {}", closing))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::diagnostic::SourceMap;
+
+	// A lexer for `code` as the only file of a source map.
+	fn lexer(code: &str) -> Lexer<'_> {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", code);
+
+		Lexer::new(source_map.file(file))
+	}
+
+	fn range(span: Span) -> (usize, usize) {
+		(span.start(), span.end())
+	}
+
+	// Every token of `code` but the `End` one.
+	fn lex_tokens(code: &str) -> Vec<Token<'_>> {
+		lexer(code).map(Result::unwrap).filter(|token| token.kind() != TokenKind::End).collect()
+	}
+
+	// The decoded value of the single string literal in `code`, or the code of the error lexing it.
+	fn lex_string(code: &str) -> Result<String, &'static str> {
+		match lexer(code).lex() {
+			Ok(token) => match token.value() {
+				Some(TokenValue::String(string)) => Ok(string.clone()),
+				_ => panic!("`{}` is not a string literal", code)
+			},
+			Err(diagnostic) => Err(diagnostic.code())
+		}
+	}
+
+	#[test]
+	fn escapes_are_decoded() {
+		assert_eq!(lex_string(r#""say \"hi\"\n\t\\ \0\'""#), Ok(String::from("say \"hi\"\n\t\\ \0'")));
+		assert_eq!(lex_string(r#""\u{48}\u{1F600}""#), Ok(String::from("H\u{1F600}")));
+		assert_eq!(lex_string("\"one\ntwo \\\n    three\""), Ok(String::from("one\ntwo three")));
+	}
+
+	#[test]
+	fn raw_strings_keep_their_content() {
+		assert_eq!(lex_string(r#"r"\n""#), Ok(String::from("\\n")));
+		assert_eq!(lex_string(r###"r##"a "# b"##"###), Ok(String::from("a \"# b")));
+		assert_eq!(lex_string(r##"r#"a""##), Err("E0002"));
+	}
+
+	#[test]
+	fn invalid_escapes_are_pointed_at() {
+		let diagnostic = lexer(r#""ab\qc" 1"#).lex().err().unwrap();
+		assert_eq!((diagnostic.code(), range(diagnostic.primary_label().span())), ("E0003", (3, 5)));
+
+		for code in [r#""\u{D800}""#, r#""\u41""#, r#""\u{}""#, r#""\u{1234567}""#, r#""\u{41""#] {
+			assert_eq!(lex_string(code), Err("E0004"), "{}", code);
+		}
+
+		// Lexing resumes after the string.
+		let lexer = lexer(r#""\q" 1"#);
+		assert!(lexer.lex().is_err());
+		assert!(lexer.lex().is_ok_and(|token| token.kind() == TokenKind::IntegerLiteral));
+	}
+
+	#[test]
+	fn interpolations_split_strings() {
+		let tokens = lex_tokens(r#""a ${b + { 1 }} c ${"${d}"}" e"#);
+		let tokens = tokens.iter().map(Token::text).collect::<Vec<_>>();
+
+		assert_eq!(tokens, vec![r#""a ${"#, "b", "+", "{", "1", "}", "} c ${", r#""${"#, "d", r#"}""#, r#"}""#, "e"]);
+		assert_eq!(lex_string(r#""\${a}""#), Ok(String::from("${a}")));
+
+		let lexer = lexer(r#""a ${b} c"#);
+		assert!(lexer.lex().is_ok() && lexer.lex().is_ok());
+		assert_eq!(lexer.lex().err().map(|diagnostic| diagnostic.code()), Some("E0002"));
+	}
+
+	#[test]
+	fn characters_are_single_and_decoded() {
+		let character = |code: &str| match lexer(code).lex() {
+			Ok(token) => Ok(token.value().cloned()),
+			Err(diagnostic) => Err(diagnostic.code())
+		};
+
+		for (code, expected) in [("'a'", 'a'), (r"'\n'", '\n'), (r"'\''", '\''), ("'\u{1F600}'", '\u{1F600}'), (r"'\u{E9}'", '\u{E9}')] {
+			assert_eq!(character(code), Ok(Some(TokenValue::Character(expected))), "{}", code);
+		}
+
+		assert_eq!(character("''"), Err("E0006"));
+		assert_eq!(character("'ab'"), Err("E0007"));
+		assert_eq!(character("'a"), Err("E0005"));
+		assert_eq!(character("'\u{E9}\n 1"), Err("E0005"));
+		assert_eq!(character(r"'\q'"), Err("E0003"));
+	}
+
+	#[test]
+	fn tokens_after_multi_byte_characters_keep_their_place() {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", "let \u{E9}t\u{E9} = \"\u{1F600}\"\n\t\u{4E16} + 1");
+		let source_file = source_map.file(file);
+
+		// Lines and columns are counted from 0 here, with columns in characters.
+		let tokens = Lexer::new(source_file)
+			.map(Result::unwrap)
+			.map(|token| (format!("{:?}", token), source_file.locate(token.span().start())))
+			.collect::<Vec<_>>();
+
+		let expected = [
+			("LetKeyword \"let\" at 0..3", (0, 0)),
+			("Identifier \"\u{E9}t\u{E9}\" at 4..9", (0, 4)),
+			("AssignmentOperator \"=\" at 10..11", (0, 8)),
+			("StringLiteral \"\\\"\u{1F600}\\\"\" at 12..18", (0, 10)),
+			("Identifier \"\u{4E16}\" at 20..23", (1, 1)),
+			("PlusOperator \"+\" at 24..25", (1, 3)),
+			("IntegerLiteral \"1\" at 26..27", (1, 5)),
+			("End \"\" at 27..27", (1, 6))
+		];
+
+		assert_eq!(tokens, expected.map(|(token, location)| (token.to_string(), location)));
+	}
+
+	#[test]
+	fn comments_are_skipped_and_nest() {
+		let tokens = lex_tokens("1 // one\n/* two /* three */ */ 2 /*/ 4 */ / 3 //");
+		let tokens = tokens.iter().map(Token::text).collect::<Vec<_>>();
+
+		assert_eq!(tokens, vec!["1", "2", "/", "3"]);
+
+		// The unterminated comment is pointed at where it opens.
+		let diagnostic = lexer("/* a /* b */").lex().err().unwrap();
+		assert_eq!((diagnostic.code(), range(diagnostic.primary_label().span())), ("E0008", (0, 2)));
+
+		let lexer = lexer("x /* a\n/* b */ c\n/* d");
+		assert!(lexer.lex().is_ok());
+		assert_eq!(lexer.lex().err().map(|diagnostic| range(diagnostic.primary_label().span())), Some((17, 19)));
+	}
+
+	#[test]
+	fn number_literals_are_decoded() {
+		let number = |code: &str| match lexer(code).lex() {
+			Ok(token) => Ok(token.value().cloned()),
+			Err(diagnostic) => Err(diagnostic.code())
+		};
+
+		for (code, expected) in [("1_000_000", 1_000_000), ("0xFf", 255), ("0b1010", 10), ("0o17", 15), ("10i32", 10), ("255u8", 255), ("0x1_F", 31)] {
+			assert_eq!(number(code), Ok(Some(TokenValue::Integer(expected))), "{}", code);
+		}
+
+		for (code, expected) in [("6.02e23", 6.02e23), ("1e-3", 0.001), ("2.5E+2", 250.0), ("10f64", 10.0), ("2.5f32", 2.5), ("0.1f32", 0.1)] {
+			assert_eq!(number(code), Ok(Some(TokenValue::Float(expected))), "{}", code);
+		}
+
+		for (code, error) in [("0b102", "E0009"), ("0o8", "E0009"), ("0x", "E0010"), ("1e+", "E0010"), ("10px", "E0011"), ("2.5i32", "E0011")] {
+			assert_eq!(number(code), Err(error), "{}", code);
+		}
+
+		for code in ["9223372036854775808", "256u8", "1e400", "1e39f32"] {
+			assert_eq!(number(code), Err("E0012"), "{}", code);
+		}
+
+		// Neither the `..` of a range nor a method call continues the literal.
+		let lexer = lexer("0..1.len()");
+		assert_eq!(lexer.lex().ok().map(|token| token.text()), Some("0"));
+		assert_eq!(lexer.lex().ok().map(|token| token.kind()), Some(TokenKind::RangeOperator));
+		assert_eq!(lexer.lex().ok().map(|token| token.text()), Some("1"));
+	}
+
+	#[test]
+	fn trivia_rebuilds_the_source() {
+		let code = "/// Doc.\r\nfn f(a) {\n\t/* b /* c */ */ a + 0x1F // d\n}\n\"x ${ f(1) }\"  \n";
+		let tokens = lexer(code).with_trivia().map(Result::unwrap).collect::<Vec<_>>();
+
+		let rebuilt = tokens.iter()
+			.flat_map(|token| token.trivia().iter().map(Trivia::text).chain([token.text()]))
+			.collect::<String>();
+
+		assert_eq!(rebuilt, code);
+		assert_eq!(tokens[0].trivia().iter().map(Trivia::kind).collect::<Vec<_>>(), vec![TriviaKind::DocComment, TriviaKind::Newline]);
+		assert_eq!(tokens.last().map(|token| token.trivia().len()), Some(2));
+		assert!(lexer(code).all(|result| result.is_ok_and(|token| token.trivia().is_empty())));
+	}
+}
@@ -1,8 +1,15 @@
 mod token;
+mod trivia;
 mod lexer;
 
 pub use token::{
 	Token,
-	TokenKind
+	TokenKind,
+	TokenValue,
+	KEYWORDS
+};
+pub use trivia::{
+	Trivia,
+	TriviaKind
 };
 pub use lexer::Lexer;
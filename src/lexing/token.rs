@@ -1,4 +1,8 @@
-#[derive(Clone, Copy, PartialEq, Eq)]
+use std::fmt;
+use crate::lexing::Trivia;
+use crate::diagnostic::Span;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TokenKind {
 // Literals
 	StringLiteral,
@@ -6,6 +10,11 @@ pub enum TokenKind {
 	IntegerLiteral,
 	FloatingPointLiteral,
 
+// Interpolated strings, split at the expressions in them as in `"a ${` b `} c ${` d `} e"`
+	InterpolationStart,
+	InterpolationMiddle,
+	InterpolationEnd,
+
 // Arithmetic
 	PlusOperator,
 	MinusOperator,
@@ -29,31 +38,112 @@ pub enum TokenKind {
 	LogicalAndOperator,
 	LogicalOrOperator,
 
+// Range
+	RangeOperator,
+
 // Separator
 	OpenParenthesis,
 	CloseParenthesis,
+	OpenBrace,
+	CloseBrace,
+	OpenBracket,
+	CloseBracket,
+	Semicolon,
+	Comma,
+	Colon,
+	Arrow,
+	Dot,
 
 	Identifier,
-	Keyword,
+
+// Keywords
+	LetKeyword,
+	FnKeyword,
+	ReturnKeyword,
+	IfKeyword,
+	ElseKeyword,
+	WhileKeyword,
+	ForKeyword,
+	InKeyword,
+	BreakKeyword,
+	ContinueKeyword,
+	TrueKeyword,
+	FalseKeyword,
+	StructKeyword,
 
 	End
 }
 
+// Reserved words, which are lexed as keywords rather than identifiers.
+pub const KEYWORDS: [(&str, TokenKind); 13] = [
+	("let", TokenKind::LetKeyword),
+	("fn", TokenKind::FnKeyword),
+	("return", TokenKind::ReturnKeyword),
+	("if", TokenKind::IfKeyword),
+	("else", TokenKind::ElseKeyword),
+	("while", TokenKind::WhileKeyword),
+	("for", TokenKind::ForKeyword),
+	("in", TokenKind::InKeyword),
+	("break", TokenKind::BreakKeyword),
+	("continue", TokenKind::ContinueKeyword),
+	("true", TokenKind::TrueKeyword),
+	("false", TokenKind::FalseKeyword),
+	("struct", TokenKind::StructKeyword)
+];
+
+impl TokenKind {
+	pub fn is_keyword(&self) -> bool {
+		KEYWORDS.iter().any(|(_, kind)| kind == self)
+	}
+}
+
+// What a literal stands for, decoded by the lexer so nothing after it has to look at the literal's text.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TokenValue {
+	String(String),
+	Character(char),
+	Integer(i64),
+	Float(f64)
+}
+
 pub struct Token<'a> {
-	index: usize,
+	span: Span,
 	text: &'a str,
-	kind: TokenKind
+	kind: TokenKind,
+	value: Option<TokenValue>,
+	// The lines of the `///` comments right before the token, without the slashes.
+	documentation: Vec<&'a str>,
+	// Everything between the previous token and this one, only kept by a lexer made `with_trivia`.
+	trivia: Vec<Trivia<'a>>
 }
 
 impl<'a> Token<'a> {
-	pub fn new(index: usize, text: &'a str, kind: TokenKind) -> Self {
+	pub fn new(span: Span, text: &'a str, kind: TokenKind) -> Self {
 		Self {
-			index,
+			span,
 			text,
-			kind
+			kind,
+			value: None,
+			documentation: Vec::new(),
+			trivia: Vec::new()
 		}
 	}
 
+	pub fn with_value(mut self, value: TokenValue) -> Self {
+		self.value = Some(value);
+		self
+	}
+
+	pub fn with_documentation(mut self, documentation: Vec<&'a str>) -> Self {
+		self.documentation = documentation;
+		self
+	}
+
+	pub fn with_trivia(mut self, trivia: Vec<Trivia<'a>>) -> Self {
+		self.trivia = trivia;
+		self
+	}
+
 	pub fn text(&self) -> &'a str {
 		self.text
 	}
@@ -61,4 +151,27 @@ impl<'a> Token<'a> {
 	pub fn kind(&self) -> TokenKind {
 		self.kind
 	}
+
+	pub fn value(&self) -> Option<&TokenValue> {
+		self.value.as_ref()
+	}
+
+	pub fn documentation(&self) -> &[&'a str] {
+		&self.documentation
+	}
+
+	pub fn trivia(&self) -> &[Trivia<'a>] {
+		&self.trivia
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
+
+// Shows the token as `Identifier "x" at 4..5`, by the bytes it spans. The source map turns those into lines and columns.
+impl<'a> fmt::Debug for Token<'a> {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "{:?} {:?} at {}..{}", self.kind, self.text, self.span.start(), self.span.end())
+	}
 }
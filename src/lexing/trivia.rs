@@ -0,0 +1,32 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriviaKind {
+	// Any run of white space other than line breaks.
+	WhiteSpace,
+	Newline,
+	LineComment,
+	DocComment,
+	BlockComment
+}
+
+// White space or a comment between tokens, which only tools that rebuild the source look at.
+pub struct Trivia<'a> {
+	kind: TriviaKind,
+	text: &'a str
+}
+
+impl<'a> Trivia<'a> {
+	pub fn new(kind: TriviaKind, text: &'a str) -> Self {
+		Self {
+			kind,
+			text
+		}
+	}
+
+	pub fn kind(&self) -> TriviaKind {
+		self.kind
+	}
+
+	pub fn text(&self) -> &'a str {
+		self.text
+	}
+}
@@ -2,33 +2,28 @@ mod lexing;
 mod syntax;
 mod parser;
 mod diagnostic;
+mod checker;
 mod runtime;
 mod types;
+mod cli;
 
-use lexing::{
-	TokenKind,
-	Lexer
-};
-use parser::Parser;
-use diagnostic::print_syntax;
-use runtime::evaluate_syntax;
+use std::env;
+use std::thread;
+use std::process::ExitCode;
 
-fn main() {
-	let code = "(10 * 32.2 - 12) / 12 - 4 + (44 - 23) + 5 - 12";
-	let lexer = Lexer::new(code);
-	let mut tokens = Vec::new();
+// Parsing and evaluation recurse along the program, deeply nested code and recursive functions need more
+// than the stack the main thread starts with.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
 
-	loop {
-		let token = lexer.lex().unwrap();
-		if token.kind() == TokenKind::End {
-			break;
-		} else {
-			tokens.push(token);
-		}
-	}
+fn main() -> ExitCode {
+	let arguments: Vec<String> = env::args().skip(1).collect();
 
-	let parser = Parser::new(&tokens);
-	let syntax = parser.parse();
-	print_syntax(&syntax);
-	println!("{} = {}", code, evaluate_syntax(&syntax));
+	thread::Builder::new()
+		.stack_size(STACK_SIZE)
+		.spawn(move || cli::run(&arguments))
+		.and_then(|interpreter| interpreter.join().map_err(|_| std::io::Error::other("the interpreter panicked")))
+		.unwrap_or_else(|error| {
+			eprintln!("error: {}", error);
+			ExitCode::FAILURE
+		})
 }
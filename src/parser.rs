@@ -1,31 +1,77 @@
 use std::rc::Rc;
-use std::cell::Cell;
+use std::cell::{
+	Cell,
+	RefCell
+};
 use crate::syntax::{
 	Syntax,
 	Expression,
+	Associativity,
 	BinaryExpressionKind,
 	BinaryExpression,
 	UnaryExpressionKind,
 	UnaryExpression,
 	LiteralExpressionKind,
 	LiteralExpression,
-	ParenthesisedExpression
+	ParenthesisedExpression,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	FieldInitialiser,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	StringPart,
+	InterpolatedStringExpression,
+	ErrorExpression,
+	Statement,
+	ExpressionStatement,
+	VariableDeclaration,
+	Block,
+	ElseBranch,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	Parameter,
+	FunctionDeclaration,
+	Field,
+	StructDeclaration,
+	ReturnStatement,
+	Program,
+	TypeAnnotation
 };
 use crate::lexing::{
 	Token,
-	TokenKind
+	TokenKind,
+	TokenValue
+};
+use crate::diagnostic::{
+	Diagnostic,
+	Span
 };
 
 pub struct Parser<'a> {
 	tokens: &'a Vec<Token<'a>>,
-	index: Cell<usize>
+	index: Cell<usize>,
+	// How many loops enclose the current token, `break` and `continue` are only allowed inside one.
+	loop_depth: Cell<usize>,
+	// Likewise for functions and `return`.
+	function_depth: Cell<usize>,
+	// Cleared in the conditions of `if` and `while` and after `in`, where `name {` starts the body rather than a struct literal.
+	struct_literals_allowed: Cell<bool>,
+	diagnostics: RefCell<Vec<Diagnostic>>
 }
 
 impl<'a> Parser<'a> {
-	pub fn new(tokens: &'a Vec<Token>) -> Self {
+	pub fn new(tokens: &'a Vec<Token<'a>>) -> Self {
 		Self {
 			tokens,
-			index: Cell::new(0)
+			index: Cell::new(0),
+			loop_depth: Cell::new(0),
+			function_depth: Cell::new(0),
+			struct_literals_allowed: Cell::new(true),
+			diagnostics: RefCell::new(Vec::new())
 		}
 	}
 
@@ -33,7 +79,11 @@ impl<'a> Parser<'a> {
 		self.index.set(self.index.get() + 1);
 	}
 
-	fn current_token(&self) -> &Token {
+	fn previous_token(&self) -> &'a Token<'a> {
+		&self.tokens[self.index.get().saturating_sub(1)]
+	}
+
+	fn current_token(&self) -> &'a Token<'a> {
 		if self.index.get() < self.tokens.len() {
 			&self.tokens[self.index.get()]
 		} else {
@@ -41,37 +91,739 @@ impl<'a> Parser<'a> {
 		}
 	}
 
-	pub fn parse(&self) -> Rc<Syntax> {
-		Rc::new(Syntax::Expression(self.parse_expression()))
+	fn report(&self, diagnostic: Diagnostic) {
+		self.diagnostics.borrow_mut().push(diagnostic);
+	}
+
+	// Tokens the parser can resume at after an error.
+	fn is_synchronisation_point(&self) -> bool {
+		matches!(
+			self.current_token().kind(),
+			TokenKind::CloseParenthesis | TokenKind::CloseBrace | TokenKind::CloseBracket |
+			TokenKind::InterpolationMiddle | TokenKind::InterpolationEnd | TokenKind::Semicolon | TokenKind::End
+		)
+	}
+
+	fn synchronise(&self) {
+		while !self.is_synchronisation_point() {
+			self.increment_index();
+		}
+	}
+
+	// Parses the whole token stream, returning the syntax tree along with every error found on the way.
+	// Parts of the tree that could not be parsed are replaced by error expressions.
+	pub fn parse(&self) -> (Rc<Syntax<'a>>, Vec<Diagnostic>) {
+		let mut statements = self.parse_statements();
+
+		while self.current_token().kind() == TokenKind::CloseBrace {
+			self.report_unmatched_delimiter("{");
+			self.increment_index();
+			statements.extend(self.parse_statements());
+		}
+
+		(Rc::new(Syntax::Program(Program::new(statements))), self.diagnostics.take())
+	}
+
+	fn report_unmatched_delimiter(&self, opening_delimiter: &str) {
+		self.report(
+			Diagnostic::error("E0103", "unmatched closing delimiter", self.current_token().span())
+				.with_primary_label(format!("no `{}` to close", opening_delimiter))
+		);
+	}
+
+	// Parses statements up to, but not including, a `}` or the end of the input.
+	fn parse_statements(&self) -> Vec<Statement<'a>> {
+		let mut statements = Vec::new();
+
+		loop {
+			match self.current_token().kind() {
+				TokenKind::CloseBrace |
+				TokenKind::End => return statements,
+
+				// Empty statement.
+				TokenKind::Semicolon => self.increment_index(),
+
+				TokenKind::CloseParenthesis => {
+					self.report_unmatched_delimiter("(");
+					self.increment_index();
+				},
+
+				TokenKind::CloseBracket => {
+					self.report_unmatched_delimiter("[");
+					self.increment_index();
+				},
+
+				TokenKind::InterpolationMiddle |
+				TokenKind::InterpolationEnd => {
+					self.report_unmatched_delimiter("${");
+					self.increment_index();
+				},
+
+				_ => statements.push(self.parse_statement())
+			}
+		}
+	}
+
+	fn parse_statement(&self) -> Statement<'a> {
+		if let Some(block) = self.parse_block() {
+			return Statement::Block(block);
+		}
+
+		if let Some(variable_declaration) = self.parse_variable_declaration() {
+			return variable_declaration;
+		}
+
+		if let Some(if_statement) = self.parse_if_statement() {
+			return Statement::If(if_statement);
+		}
+
+		if let Some(while_statement) = self.parse_while_statement() {
+			return while_statement;
+		}
+
+		if let Some(for_statement) = self.parse_for_statement() {
+			return for_statement;
+		}
+
+		if let Some(loop_control) = self.parse_loop_control() {
+			return loop_control;
+		}
+
+		if let Some(function_declaration) = self.parse_function_declaration() {
+			return function_declaration;
+		}
+
+		if let Some(return_statement) = self.parse_return_statement() {
+			return return_statement;
+		}
+
+		if let Some(struct_declaration) = self.parse_struct_declaration() {
+			return struct_declaration;
+		}
+
+		let expression = self.parse_expression();
+
+		Statement::Expression(ExpressionStatement::new(expression, self.parse_terminator()))
+	}
+
+	// Statements end with a `;`, which can only be left out before a `}` or at the end of the input.
+	// Returns whether there was one.
+	fn parse_terminator(&self) -> bool {
+		match self.current_token().kind() {
+			TokenKind::Semicolon => {
+				self.increment_index();
+				true
+			},
+			TokenKind::CloseBrace |
+			TokenKind::End => false,
+			_ => {
+				self.report_expected("`;`");
+				false
+			}
+		}
+	}
+
+	fn parse_block(&self) -> Option<Block<'a>> {
+		if self.current_token().kind() != TokenKind::OpenBrace {
+			return None;
+		}
+
+		let open_brace = self.current_token();
+		self.increment_index();
+
+		let statements = self.parse_statements();
+
+		if self.current_token().kind() == TokenKind::CloseBrace {
+			self.increment_index();
+		} else {
+			self.report_unclosed_brace(open_brace);
+		}
+
+		Some(Block::new(statements))
+	}
+
+	fn report_unclosed_brace(&self, open_brace: &Token) {
+		self.report(
+			Diagnostic::error("E0101", "unclosed delimiter", self.current_token().span())
+				.with_primary_label("expected `}`, found end of input")
+				.with_label(open_brace.span(), "unclosed delimiter opened here")
+		);
+	}
+
+	// Parses an expression where a `{` must start a body, as the condition of an `if`.
+	fn parse_condition(&self) -> Expression<'a> {
+		let struct_literals_allowed = self.struct_literals_allowed.replace(false);
+		let condition = self.parse_expression();
+		self.struct_literals_allowed.set(struct_literals_allowed);
+
+		condition
+	}
+
+	// Parses an expression inside delimiters, where struct literals are unambiguous again.
+	fn parse_delimited_expression(&self) -> Expression<'a> {
+		let struct_literals_allowed = self.struct_literals_allowed.replace(true);
+		let expression = self.parse_expression();
+		self.struct_literals_allowed.set(struct_literals_allowed);
+
+		expression
+	}
+
+	// The block after `if`, `else`, `while` and `for`, which can not be left out.
+	fn parse_body(&self) -> Block<'a> {
+		if let Some(block) = self.parse_block() {
+			return block;
+		}
+
+		self.report_expected("`{`");
+		self.synchronise();
+
+		Block::new(Vec::new())
+	}
+
+	fn parse_if_statement(&self) -> Option<IfStatement<'a>> {
+		if self.current_token().kind() != TokenKind::IfKeyword {
+			return None;
+		}
+
+		self.increment_index();
+
+		let condition = self.parse_condition();
+		let consequence = self.parse_body();
+		let mut alternative = None;
+
+		if self.current_token().kind() == TokenKind::ElseKeyword {
+			self.increment_index();
+
+			alternative = match self.parse_if_statement() {
+				Some(if_statement) => Some(ElseBranch::If(Box::new(if_statement))),
+				None => Some(ElseBranch::Block(self.parse_body()))
+			};
+		}
+
+		Some(IfStatement::new(condition, consequence, alternative))
+	}
+
+	fn parse_while_statement(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::WhileKeyword {
+			return None;
+		}
+
+		self.increment_index();
+
+		let condition = self.parse_condition();
+		let body = self.parse_loop_body();
+
+		Some(Statement::While(WhileStatement::new(condition, body)))
+	}
+
+	fn parse_for_statement(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::ForKeyword {
+			return None;
+		}
+
+		let keyword = self.current_token();
+		self.increment_index();
+
+		let variable = self.current_token();
+
+		if !self.parse_name("a variable name") {
+			self.synchronise();
+
+			return Some(self.parse_error_statement(keyword.span().to(variable.span())));
+		}
+
+		if self.current_token().kind() == TokenKind::InKeyword {
+			self.increment_index();
+		} else {
+			self.report_expected("`in`");
+		}
+
+		let iterable = self.parse_condition();
+		let body = self.parse_loop_body();
+
+		Some(Statement::For(ForStatement::new(variable, iterable, body)))
+	}
+
+	fn parse_loop_body(&self) -> Block<'a> {
+		self.loop_depth.set(self.loop_depth.get() + 1);
+		let body = self.parse_body();
+		self.loop_depth.set(self.loop_depth.get() - 1);
+
+		body
+	}
+
+	// `break` and `continue`.
+	fn parse_loop_control(&self) -> Option<Statement<'a>> {
+		let statement = match self.current_token().kind() {
+			TokenKind::BreakKeyword => Statement::Break,
+			TokenKind::ContinueKeyword => Statement::Continue,
+			_ => return None
+		};
+
+		let keyword = self.current_token();
+
+		if self.loop_depth.get() == 0 {
+			self.report(
+				Diagnostic::error("E0109", format!("`{}` outside of a loop", keyword.text()), keyword.span())
+					.with_primary_label(format!("cannot `{}` outside of a loop", keyword.text()))
+			);
+		}
+
+		self.increment_index();
+		self.parse_terminator();
+
+		Some(statement)
+	}
+
+	fn parse_function_declaration(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::FnKeyword {
+			return None;
+		}
+
+		let keyword = self.current_token();
+		self.increment_index();
+
+		let name = self.current_token();
+
+		if !self.parse_name("a function name") {
+			self.synchronise();
+
+			return Some(self.parse_error_statement(keyword.span().to(name.span())));
+		}
+
+		let parameters = self.parse_parameters();
+		let result = self.parse_type_annotation(TokenKind::Arrow);
+		let signature = keyword.span().to(self.previous_token().span());
+
+		// Loops around the declaration do not extend into the body.
+		let loop_depth = self.loop_depth.replace(0);
+		self.function_depth.set(self.function_depth.get() + 1);
+		let body = self.parse_body();
+		self.function_depth.set(self.function_depth.get() - 1);
+		self.loop_depth.set(loop_depth);
+
+		Some(Statement::Function(Rc::new(FunctionDeclaration::new(keyword.documentation(), name, parameters, result, body, signature))))
+	}
+
+	fn parse_parameters(&self) -> Vec<Parameter<'a>> {
+		let mut parameters = Vec::new();
+
+		if self.current_token().kind() != TokenKind::OpenParenthesis {
+			self.report_expected("`(`");
+			return parameters;
+		}
+
+		self.increment_index();
+
+		while self.current_token().kind() != TokenKind::CloseParenthesis {
+			let parameter = self.current_token();
+
+			if !self.parse_name("a parameter name") {
+				self.synchronise();
+				break;
+			}
+
+			parameters.push(Parameter::new(parameter, self.parse_type_annotation(TokenKind::Colon)));
+
+			if !self.parse_list_separator() {
+				break;
+			}
+		}
+
+		if self.current_token().kind() == TokenKind::CloseParenthesis {
+			self.increment_index();
+		}
+
+		parameters
+	}
+
+	// Parameters and arguments are separated by `,`, a trailing one is allowed.
+	// Returns whether the list goes on.
+	fn parse_list_separator(&self) -> bool {
+		match self.current_token().kind() {
+			TokenKind::Comma => {
+				self.increment_index();
+				true
+			},
+			TokenKind::CloseParenthesis => false,
+			_ => {
+				self.report_expected("`,` or `)`");
+				self.synchronise();
+				false
+			}
+		}
+	}
+
+	fn parse_return_statement(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::ReturnKeyword {
+			return None;
+		}
+
+		let keyword = self.current_token();
+
+		if self.function_depth.get() == 0 {
+			self.report(
+				Diagnostic::error("E0110", "`return` outside of a function", keyword.span())
+					.with_primary_label("cannot `return` outside of a function")
+			);
+		}
+
+		self.increment_index();
+
+		let value = match self.current_token().kind() {
+			TokenKind::Semicolon |
+			TokenKind::CloseBrace |
+			TokenKind::End => None,
+			_ => Some(self.parse_expression())
+		};
+
+		self.parse_terminator();
+
+		Some(Statement::Return(ReturnStatement::new(keyword, value)))
+	}
+
+	fn parse_struct_declaration(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::StructKeyword {
+			return None;
+		}
+
+		let keyword = self.current_token();
+		self.increment_index();
+
+		let name = self.current_token();
+
+		if !self.parse_name("a struct name") {
+			self.synchronise();
+
+			return Some(self.parse_error_statement(keyword.span().to(name.span())));
+		}
+
+		let fields = self.parse_braced_list(|| {
+			let name = self.current_token();
+
+			if !self.parse_name("a field name") {
+				return None;
+			}
+
+			if self.current_token().kind() != TokenKind::Colon {
+				self.report_expected("`:`");
+				return None;
+			}
+
+			Some(Field::new(name, self.parse_type_annotation(TokenKind::Colon)))
+		});
+
+		Some(Statement::Struct(Rc::new(StructDeclaration::new(keyword.documentation(), name, fields))))
+	}
+
+	// The `{`-delimited, `,`-separated items of a struct declaration or literal, a trailing `,` is allowed.
+	// `parse_item` returns `None` when it reported an error, the list is skipped from there.
+	fn parse_braced_list<T>(&self, mut parse_item: impl FnMut() -> Option<T>) -> Vec<T> {
+		let mut items = Vec::new();
+
+		if self.current_token().kind() != TokenKind::OpenBrace {
+			self.report_expected("`{`");
+			self.synchronise();
+
+			return items;
+		}
+
+		let open_brace = self.current_token();
+		self.increment_index();
+
+		loop {
+			match self.current_token().kind() {
+				TokenKind::CloseBrace => {
+					self.increment_index();
+					return items;
+				},
+				TokenKind::End => {
+					self.report_unclosed_brace(open_brace);
+					return items;
+				},
+				_ => ()
+			}
+
+			match parse_item() {
+				Some(item) => items.push(item),
+				None => break
+			}
+
+			match self.current_token().kind() {
+				TokenKind::Comma => self.increment_index(),
+				TokenKind::CloseBrace => (),
+				_ => {
+					self.report_expected("`,` or `}`");
+					break;
+				}
+			}
+		}
+
+		self.synchronise();
+
+		if self.current_token().kind() == TokenKind::CloseBrace {
+			self.increment_index();
+		}
+
+		items
+	}
+
+	// Stands in for a statement that could not be parsed.
+	fn parse_error_statement(&self, span: Span) -> Statement<'a> {
+		let expression = Expression::Error(ErrorExpression::new(span));
+
+		Statement::Expression(ExpressionStatement::new(expression, self.parse_terminator()))
+	}
+
+	// Consumes the name of a variable, function, parameter, struct or field, returning whether there was one.
+	// A keyword in its place is reported but still taken as the name, so parsing carries on as intended.
+	fn parse_name(&self, expected: &str) -> bool {
+		let current_token = self.current_token();
+
+		if current_token.kind().is_keyword() {
+			self.report(
+				Diagnostic::error("E0111", format!("expected {}, found keyword `{}`", expected, current_token.text()), current_token.span())
+					.with_primary_label(format!("`{}` is a reserved keyword", current_token.text()))
+					.with_note("keywords cannot be used as names")
+			);
+		} else if current_token.kind() != TokenKind::Identifier {
+			self.report_expected(expected);
+			return false;
+		}
+
+		self.increment_index();
+		true
+	}
+
+	// Reports that `expected` was wanted in place of the current token.
+	fn report_expected(&self, expected: &str) {
+		let current_token = self.current_token();
+
+		self.report(
+			Diagnostic::error("E0107", format!("expected {}, found {}", expected, describe_token(current_token)), current_token.span())
+				.with_primary_label(format!("expected {}", expected))
+		);
+	}
+
+	fn parse_variable_declaration(&self) -> Option<Statement<'a>> {
+		if self.current_token().kind() != TokenKind::LetKeyword {
+			return None;
+		}
+
+		let keyword = self.current_token();
+		self.increment_index();
+
+		let name = self.current_token();
+
+		if !self.parse_name("a variable name") {
+			self.synchronise();
+
+			return Some(self.parse_error_statement(keyword.span().to(name.span())));
+		}
+
+		let annotation = self.parse_type_annotation(TokenKind::Colon);
+
+		if self.current_token().kind() == TokenKind::AssignmentOperator {
+			self.increment_index();
+		} else {
+			self.report_expected("`=`");
+		}
+
+		let variable_declaration = VariableDeclaration::new(keyword.documentation(), name, annotation, Rc::new(self.parse_expression()));
+		self.parse_terminator();
+
+		Some(Statement::VariableDeclaration(variable_declaration))
+	}
+
+	// An optional type, introduced by `introducer`, which is `:` after names and `->` after parameters.
+	fn parse_type_annotation(&self, introducer: TokenKind) -> Option<TypeAnnotation<'a>> {
+		if self.current_token().kind() != introducer {
+			return None;
+		}
+
+		self.increment_index();
+
+		self.parse_type()
 	}
 
-	fn parse_expression(&self) -> Expression {
-		if let Some(binary_expression) = self.parse_binary_expression() {
-			return binary_expression;
+	// A type name, or `[element]` for arrays.
+	fn parse_type(&self) -> Option<TypeAnnotation<'a>> {
+		let current_token = self.current_token();
+
+		match current_token.kind() {
+			TokenKind::Identifier => {
+				self.increment_index();
+				Some(TypeAnnotation::Named(current_token))
+			},
+			TokenKind::OpenBracket => {
+				self.increment_index();
+				let element = self.parse_type()?;
+
+				if self.current_token().kind() != TokenKind::CloseBracket {
+					self.report_expected("`]`");
+					return None;
+				}
+
+				self.increment_index();
+
+				Some(TypeAnnotation::Array {
+					element: Box::new(element),
+					span: current_token.span().to(self.previous_token().span())
+				})
+			},
+			_ => {
+				self.report_expected("a type");
+				None
+			}
+		}
+	}
+
+	// Assignment binds looser than every binary operator and associates to the right, as in `a = b = 1`.
+	fn parse_expression(&self) -> Expression<'a> {
+		let expression = self.parse_binary_expression(0);
+
+		if self.current_token().kind() != TokenKind::AssignmentOperator {
+			return expression;
+		}
+
+		let operator = self.current_token();
+		self.increment_index();
+		let value = self.parse_expression();
+
+		if expression.is_assignable() {
+			return Expression::Assignment(AssignmentExpression::new(Rc::new(expression), Rc::new(value)));
 		}
 
-		panic!("Parsing failed")
+		self.report(
+			Diagnostic::error("E0108", "invalid assignment target", expression.span())
+				.with_primary_label("only variables and their fields and elements can be assigned to")
+				.with_label(operator.span(), "assignment here")
+		);
+
+		Expression::Error(ErrorExpression::new(expression.span().to(value.span())))
 	}
 
-	fn parse_parenthesised_expression(&self) -> Option<Expression> {
+	fn parse_parenthesised_expression(&self) -> Option<Expression<'a>> {
 		match self.current_token().kind() {
 			TokenKind::OpenParenthesis => {
+				let open_parenthesis = self.current_token();
 				self.increment_index();
-				let content = self.parse_expression();
-
-				match self.current_token().kind() {
-					TokenKind::CloseParenthesis => {
-						self.increment_index();
-						Some(Expression::Parenthesised(ParenthesisedExpression::new(Rc::new(content))))
-					},
-					_ => panic!("Unclosed delimiter.")
+				let content = self.parse_delimited_expression();
+
+				if self.current_token().kind() != TokenKind::CloseParenthesis {
+					self.report_unclosed_delimiter(open_parenthesis, "`)`");
+					self.synchronise();
+				}
+
+				let mut span = open_parenthesis.span().to(content.span());
+
+				if self.current_token().kind() == TokenKind::CloseParenthesis {
+					span = span.to(self.current_token().span());
+					self.increment_index();
 				}
+
+				Some(Expression::Parenthesised(ParenthesisedExpression::new(Rc::new(content), span)))
 			},
 			_ => None
 		}
 	}
 
-	fn parse_literal_expression(&self) -> Option<Expression> {
+	fn report_unclosed_delimiter(&self, open_delimiter: &Token, expected: &str) {
+		let current_token = self.current_token();
+
+		if current_token.kind() == TokenKind::End {
+			self.report(
+				Diagnostic::error("E0101", "unclosed delimiter", current_token.span())
+					.with_primary_label(format!("expected {}, found end of input", expected))
+					.with_label(open_delimiter.span(), "unclosed delimiter opened here")
+			);
+		} else {
+			self.report(
+				Diagnostic::error("E0102", format!("unexpected {}", describe_token(current_token)), current_token.span())
+					.with_primary_label(format!("expected {}", expected))
+					.with_label(open_delimiter.span(), "to close this delimiter")
+			);
+		}
+	}
+
+	// The arguments of a call, following the callee.
+	fn parse_call_expression(&self, callee: Expression<'a>) -> Expression<'a> {
+		let (arguments, end) = self.parse_expression_list(TokenKind::CloseParenthesis);
+		let span = callee.span().to(end);
+
+		Expression::Call(CallExpression::new(Rc::new(callee), arguments, span))
+	}
+
+	fn parse_array_literal_expression(&self) -> Option<Expression<'a>> {
+		if self.current_token().kind() != TokenKind::OpenBracket {
+			return None;
+		}
+
+		let open_bracket = self.current_token();
+		let (elements, end) = self.parse_expression_list(TokenKind::CloseBracket);
+
+		Some(Expression::ArrayLiteral(ArrayLiteralExpression::new(elements, open_bracket.span().to(end))))
+	}
+
+	// The `,`-separated expressions from the current opening delimiter up to `closing`, a trailing `,` is allowed.
+	// Returns them along with the span of the last token taken, the closing delimiter unless it is missing.
+	fn parse_expression_list(&self, closing: TokenKind) -> (Vec<Expression<'a>>, Span) {
+		let open_delimiter = self.current_token();
+		let closing_text = if closing == TokenKind::CloseBracket { "`]`" } else { "`)`" };
+		self.increment_index();
+
+		let mut expressions = Vec::new();
+
+		loop {
+			match self.current_token().kind() {
+				kind if kind == closing => break,
+				TokenKind::End => {
+					self.report_unclosed_delimiter(open_delimiter, closing_text);
+					break;
+				},
+				_ => ()
+			}
+
+			expressions.push(self.parse_delimited_expression());
+
+			if self.current_token().kind() == TokenKind::Comma {
+				self.increment_index();
+			} else if self.current_token().kind() != closing {
+				self.report_unclosed_delimiter(open_delimiter, &format!("`,` or {}", closing_text));
+				self.synchronise();
+				break;
+			}
+		}
+
+		if self.current_token().kind() == closing {
+			self.increment_index();
+		}
+
+		(expressions, self.previous_token().span())
+	}
+
+	// The index or range after an array, as in `a[i]` or `a[1..3]`.
+	fn parse_index_expression(&self, object: Expression<'a>) -> Expression<'a> {
+		let open_bracket = self.current_token();
+		self.increment_index();
+		let index = self.parse_delimited_expression();
+
+		if self.current_token().kind() != TokenKind::CloseBracket {
+			self.report_unclosed_delimiter(open_bracket, "`]`");
+			self.synchronise();
+		}
+
+		let mut span = object.span().to(index.span());
+
+		if self.current_token().kind() == TokenKind::CloseBracket {
+			span = span.to(self.current_token().span());
+			self.increment_index();
+		}
+
+		Expression::Index(IndexExpression::new(Rc::new(object), Rc::new(index), span))
+	}
+
+	fn parse_literal_expression(&self) -> Option<Expression<'a>> {
 		let current_token = self.current_token();
 
 		let literal_expression_kind = match current_token.kind() {
@@ -79,6 +831,8 @@ impl<'a> Parser<'a> {
 			TokenKind::CharacterLiteral => LiteralExpressionKind::Character,
 			TokenKind::IntegerLiteral => LiteralExpressionKind::Integer,
 			TokenKind::FloatingPointLiteral => LiteralExpressionKind::FloatingPoint,
+			TokenKind::TrueKeyword |
+			TokenKind::FalseKeyword => LiteralExpressionKind::Boolean,
 			_ => return None
 		};
 
@@ -87,10 +841,91 @@ impl<'a> Parser<'a> {
 		Some(Expression::Literal(LiteralExpression::new(current_token, literal_expression_kind)))
 	}
 
-	fn parse_unary_expression(&self) -> Option<Expression> {
-		let unary_expression_kind = match self.current_token().kind() {
+	// The lexer splits `"a ${b} c"` into `"a ${`, the tokens of `b` and `} c"`.
+	fn parse_interpolated_string_expression(&self) -> Option<Expression<'a>> {
+		let start = self.current_token();
+
+		if start.kind() != TokenKind::InterpolationStart {
+			return None;
+		}
+
+		self.increment_index();
+
+		let mut parts = vec![StringPart::Text(string_value(start))];
+		let mut open_interpolation = start;
+
+		loop {
+			parts.push(StringPart::Expression(self.parse_delimited_expression()));
+
+			if !matches!(self.current_token().kind(), TokenKind::InterpolationMiddle | TokenKind::InterpolationEnd) {
+				self.report_unclosed_delimiter(open_interpolation, "`}`");
+				self.synchronise();
+			}
+
+			let current_token = self.current_token();
+
+			match current_token.kind() {
+				TokenKind::InterpolationMiddle => open_interpolation = current_token,
+				TokenKind::InterpolationEnd => (),
+				_ => break
+			}
+
+			parts.push(StringPart::Text(string_value(current_token)));
+			self.increment_index();
+
+			if current_token.kind() == TokenKind::InterpolationEnd {
+				break;
+			}
+		}
+
+		let span = start.span().to(self.previous_token().span());
+
+		Some(Expression::InterpolatedString(InterpolatedStringExpression::new(parts, span)))
+	}
+
+	fn parse_identifier_expression(&self) -> Option<Expression<'a>> {
+		let current_token = self.current_token();
+
+		if current_token.kind() != TokenKind::Identifier {
+			return None;
+		}
+
+		self.increment_index();
+
+		if self.current_token().kind() == TokenKind::OpenBrace && self.struct_literals_allowed.get() {
+			return Some(self.parse_struct_literal_expression(current_token));
+		}
+
+		Some(Expression::Identifier(IdentifierExpression::new(current_token)))
+	}
+
+	fn parse_struct_literal_expression(&self, name: &'a Token<'a>) -> Expression<'a> {
+		let fields = self.parse_braced_list(|| {
+			let field = self.current_token();
+
+			if !self.parse_name("a field name") {
+				return None;
+			}
+
+			if self.current_token().kind() == TokenKind::Colon {
+				self.increment_index();
+			} else {
+				self.report_expected("`:`");
+				return None;
+			}
+
+			Some(FieldInitialiser::new(field, self.parse_delimited_expression()))
+		});
+
+		Expression::StructLiteral(StructLiteralExpression::new(name, fields, name.span().to(self.previous_token().span())))
+	}
+
+	fn parse_unary_expression(&self) -> Option<Expression<'a>> {
+		let operator = self.current_token();
+		let unary_expression_kind = match operator.kind() {
 			TokenKind::PlusOperator => UnaryExpressionKind::Identity,
 			TokenKind::MinusOperator => UnaryExpressionKind::Negation,
+			TokenKind::LogicalNotOperator => UnaryExpressionKind::LogicalNot,
 			_ => return None
 		};
 
@@ -99,68 +934,506 @@ impl<'a> Parser<'a> {
 		Some(
 			Expression::Unary(
 				UnaryExpression::new(
-					Rc::new(self.parse_expression()),
+					Rc::new(self.parse_binary_expression(unary_expression_kind.precedence())),
+					operator,
 					unary_expression_kind
 				)
 			)
 		)
 	}
 
-	fn parse_non_binary_expression(&self) -> Expression {
+	fn try_parse_non_binary_expression(&self) -> Option<Expression<'a>> {
 		if let Some(parenthesised_expression) = self.parse_parenthesised_expression() {
-			return parenthesised_expression;
+			return Some(parenthesised_expression);
 		}
 
 		if let Some(literal_expression) = self.parse_literal_expression() {
-			return literal_expression;
+			return Some(literal_expression);
+		}
+
+		if let Some(interpolated_string_expression) = self.parse_interpolated_string_expression() {
+			return Some(interpolated_string_expression);
 		}
 
 		if let Some(unary_expression) = self.parse_unary_expression() {
-			return unary_expression;
+			return Some(unary_expression);
+		}
+
+		if let Some(identifier_expression) = self.parse_identifier_expression() {
+			return Some(identifier_expression);
+		}
+
+		if let Some(array_literal_expression) = self.parse_array_literal_expression() {
+			return Some(array_literal_expression);
 		}
 
-		panic!("Parsing failed");
+		None
 	}
 
-	fn parse_binary_expression(&self) -> Option<Expression> {
-		let left_operand = self.parse_non_binary_expression();
+	// Calls, field accesses and indexing bind tighter than anything else, as in `-f(1).x[2]`.
+	fn parse_non_binary_expression(&self) -> Expression<'a> {
+		let mut expression = self.parse_primary_expression();
 
-		let binary_expression_kind = match self.current_token().kind() {
-			TokenKind::PlusOperator => BinaryExpressionKind::Addition,
-			TokenKind::MinusOperator => BinaryExpressionKind::Substraction,
-			TokenKind::SlashOperator => BinaryExpressionKind::Division,
-			TokenKind::StarOperator => BinaryExpressionKind::Multiplication,
-			TokenKind::PercentageOperator => BinaryExpressionKind::Modulo,
-			_ => return Some(left_operand)
-		};
+		loop {
+			match self.current_token().kind() {
+				TokenKind::OpenParenthesis => expression = self.parse_call_expression(expression),
+				TokenKind::OpenBracket => expression = self.parse_index_expression(expression),
+				TokenKind::Dot => {
+					self.increment_index();
+					let field = self.current_token();
+
+					if !self.parse_name("a field name") {
+						break;
+					}
+
+					expression = Expression::FieldAccess(FieldAccessExpression::new(Rc::new(expression), field));
+				},
+				_ => break
+			}
+		}
+
+		expression
+	}
 
+	fn parse_primary_expression(&self) -> Expression<'a> {
+		if let Some(expression) = self.try_parse_non_binary_expression() {
+			return expression;
+		}
+
+		let unexpected_token = self.current_token();
+		let mut span = unexpected_token.span();
+
+		self.report(
+			Diagnostic::error("E0100", format!("expected an expression, found {}", describe_token(unexpected_token)), span)
+				.with_primary_label("expected an expression")
+		);
+
+		if self.is_synchronisation_point() {
+			return Expression::Error(ErrorExpression::new(span));
+		}
+
+		// Skip the token that can not start an expression and try again, so `1 + * 2` only reports the `*`.
 		self.increment_index();
-		let right_operand = self.parse_expression();
 
-		if let Expression::Binary(ref new_right_operand) = right_operand {
-			if binary_expression_kind.precedence() > new_right_operand.kind().precedence() {
-				let new_left_operand = Rc::new(
-					Expression::Binary(
-						BinaryExpression::new(
-							Rc::new(left_operand),
-							new_right_operand.left_operand_rc(),
-							binary_expression_kind
-						)
-					)
-				);
+		match self.try_parse_non_binary_expression() {
+			Some(expression) => expression,
+			None => {
+				span = Span::new(span.file(), span.start(), self.current_token().span().start());
+				Expression::Error(ErrorExpression::new(span))
+			}
+		}
+	}
+
+	// Precedence climbing, only operators binding at least as tight as `minimum_precedence` are parsed here.
+	fn parse_binary_expression(&self, minimum_precedence: u8) -> Expression<'a> {
+		let mut left_operand = self.parse_non_binary_expression();
+
+		while let Some(binary_expression_kind) = binary_operator_kind(self.current_token().kind()) {
+			if binary_expression_kind.precedence() < minimum_precedence {
+				break;
+			}
+
+			let operator = self.current_token();
+			self.increment_index();
+
+			// Every binary operator is either left associative or does not associate at all,
+			// so the right operand only takes operators binding tighter than this one.
+			let right_operand = self.parse_binary_expression(binary_expression_kind.precedence() + 1);
 
-				return Some(
-					Expression::Binary(
-						BinaryExpression::new(
-							new_left_operand,
-							new_right_operand.right_operand_rc(),
-							new_right_operand.kind()
-						)
-					)
-				);
+			if binary_expression_kind.associativity() == Associativity::None {
+				let next_kind = binary_operator_kind(self.current_token().kind())
+					.filter(|next_kind| next_kind.precedence() == binary_expression_kind.precedence());
+
+				if let Some(next_kind) = next_kind {
+					self.report(
+						Diagnostic::error("E0105", format!("`{}` and `{}` cannot be chained", binary_expression_kind.symbol(), next_kind.symbol()), self.current_token().span())
+							.with_label(operator.span(), "first operator here")
+							.with_note("use parentheses to group the operations")
+					);
+				}
 			}
+
+			left_operand = Expression::Binary(BinaryExpression::new(Rc::new(left_operand), Rc::new(right_operand), operator, binary_expression_kind));
+		}
+
+		left_operand
+	}
+}
+
+fn binary_operator_kind(token_kind: TokenKind) -> Option<BinaryExpressionKind> {
+	Some(match token_kind {
+		TokenKind::PlusOperator => BinaryExpressionKind::Addition,
+		TokenKind::MinusOperator => BinaryExpressionKind::Substraction,
+		TokenKind::SlashOperator => BinaryExpressionKind::Division,
+		TokenKind::StarOperator => BinaryExpressionKind::Multiplication,
+		TokenKind::PercentageOperator => BinaryExpressionKind::Modulo,
+		TokenKind::EqualityOperator => BinaryExpressionKind::Equality,
+		TokenKind::NotEqualityOperator => BinaryExpressionKind::NotEquality,
+		TokenKind::LessThanOperator => BinaryExpressionKind::LessThan,
+		TokenKind::GreaterThanOperator => BinaryExpressionKind::GreaterThan,
+		TokenKind::LessThanEqualToOperator => BinaryExpressionKind::LessThanEqualTo,
+		TokenKind::GreaterThanEqualToOperator => BinaryExpressionKind::GreaterThanEqualTo,
+		TokenKind::LogicalAndOperator => BinaryExpressionKind::LogicalAnd,
+		TokenKind::LogicalOrOperator => BinaryExpressionKind::LogicalOr,
+		TokenKind::RangeOperator => BinaryExpressionKind::Range,
+		_ => return None
+	})
+}
+
+// The decoded text of a string literal, or of a piece of an interpolated string.
+fn string_value<'a>(token: &'a Token<'a>) -> &'a str {
+	match token.value() {
+		Some(TokenValue::String(string)) => string,
+		_ => ""
+	}
+}
+
+fn describe_token(token: &Token) -> String {
+	match token.kind() {
+		TokenKind::End => String::from("end of input"),
+		kind if kind.is_keyword() => format!("keyword `{}`", token.text()),
+		_ => format!("`{}`", token.text())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::lexing::Lexer;
+	use crate::diagnostic::SourceMap;
+
+	const BINARY_OPERATORS: [BinaryExpressionKind; 14] = [
+		BinaryExpressionKind::Addition,
+		BinaryExpressionKind::Substraction,
+		BinaryExpressionKind::Multiplication,
+		BinaryExpressionKind::Division,
+		BinaryExpressionKind::Modulo,
+		BinaryExpressionKind::Equality,
+		BinaryExpressionKind::NotEquality,
+		BinaryExpressionKind::LessThan,
+		BinaryExpressionKind::GreaterThan,
+		BinaryExpressionKind::LessThanEqualTo,
+		BinaryExpressionKind::GreaterThanEqualTo,
+		BinaryExpressionKind::LogicalAnd,
+		BinaryExpressionKind::LogicalOr,
+		BinaryExpressionKind::Range
+	];
+
+	const UNARY_OPERATORS: [UnaryExpressionKind; 3] = [
+		UnaryExpressionKind::Identity,
+		UnaryExpressionKind::Negation,
+		UnaryExpressionKind::LogicalNot
+	];
+
+	fn lex(code: &str) -> Vec<Token<'_>> {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", code);
+
+		Lexer::new(source_map.file(file)).map(|result| result.unwrap_or_else(|_| panic!("`{}` failed to lex", code))).collect()
+	}
+
+	// Fully parenthesised form of an expression, parentheses from the source are kept as `[...]`.
+	fn shape(expression: &Expression) -> String {
+		match expression {
+			Expression::Binary(binary_expression) => format!(
+				"({} {} {})",
+				shape(binary_expression.left_operand()),
+				binary_expression.kind().symbol(),
+				shape(binary_expression.right_operand())
+			),
+			Expression::Unary(unary_expression) => format!("({}{})", unary_expression.kind().symbol(), shape(unary_expression.operand())),
+			Expression::Literal(literal_expression) => literal_expression.token().text().to_string(),
+			Expression::Parenthesised(parenthesised_expression) => format!("[{}]", shape(parenthesised_expression.content())),
+			Expression::Identifier(identifier_expression) => identifier_expression.name().to_string(),
+			Expression::Assignment(assignment_expression) => format!("({} = {})", shape(assignment_expression.target()), shape(assignment_expression.value())),
+			Expression::Call(call_expression) => format!(
+				"{}({})",
+				shape(call_expression.callee()),
+				call_expression.arguments().iter().map(shape).collect::<Vec<_>>().join(", ")
+			),
+			Expression::StructLiteral(struct_literal_expression) => format!(
+				"{} {{ {} }}",
+				struct_literal_expression.name(),
+				struct_literal_expression.fields().iter()
+					.map(|field| format!("{}: {}", field.name(), shape(field.value())))
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+			Expression::FieldAccess(field_access_expression) => format!("{}.{}", shape(field_access_expression.object()), field_access_expression.field()),
+			Expression::ArrayLiteral(array_literal_expression) => format!("array({})", array_literal_expression.elements().iter().map(shape).collect::<Vec<_>>().join(", ")),
+			Expression::Index(index_expression) => format!("{}[{}]", shape(index_expression.object()), shape(index_expression.index())),
+			Expression::InterpolatedString(interpolated_string_expression) => format!(
+				"\"{}\"",
+				interpolated_string_expression.parts().iter()
+					.map(|part| match part {
+						StringPart::Text(text) => text.to_string(),
+						StringPart::Expression(expression) => format!("${{{}}}", shape(expression))
+					})
+					.collect::<String>()
+			),
+			Expression::Error(_) => String::from("<error>")
 		}
+	}
+
+	fn statement_shape(statement: &Statement) -> String {
+		match statement {
+			Statement::Expression(expression_statement) => {
+				format!("{}{}", shape(expression_statement.expression()), if expression_statement.terminated() { ";" } else { "" })
+			},
+			Statement::VariableDeclaration(variable_declaration) => {
+				format!("let {}{} = {};", variable_declaration.name(), annotation_shape(": ", variable_declaration.annotation()), shape(variable_declaration.value()))
+			},
+			Statement::Block(block) => block_shape(block),
+			Statement::If(if_statement) => if_shape(if_statement),
+			Statement::While(while_statement) => {
+				format!("while {} {}", shape(while_statement.condition()), block_shape(while_statement.body()))
+			},
+			Statement::For(for_statement) => {
+				format!("for {} in {} {}", for_statement.variable(), shape(for_statement.iterable()), block_shape(for_statement.body()))
+			},
+			Statement::Break => String::from("break;"),
+			Statement::Continue => String::from("continue;"),
+			Statement::Function(function_declaration) => format!(
+				"fn {}({}){} {}",
+				function_declaration.name(),
+				function_declaration.parameters().iter()
+					.map(|parameter| format!("{}{}", parameter.name(), annotation_shape(": ", parameter.annotation())))
+					.collect::<Vec<_>>()
+					.join(", "),
+				annotation_shape(" -> ", function_declaration.result()),
+				block_shape(function_declaration.body())
+			),
+			Statement::Return(return_statement) => match return_statement.value() {
+				Some(value) => format!("return {};", shape(value)),
+				None => String::from("return;")
+			},
+			Statement::Struct(struct_declaration) => format!(
+				"struct {} {{ {} }}",
+				struct_declaration.name(),
+				struct_declaration.fields().iter()
+					.map(|field| format!("{}{}", field.name(), annotation_shape(": ", field.annotation())))
+					.collect::<Vec<_>>()
+					.join(", ")
+			)
+		}
+	}
+
+	fn annotation_shape(introducer: &str, annotation: Option<&TypeAnnotation>) -> String {
+		annotation.map_or(String::new(), |annotation| format!("{}{}", introducer, annotation))
+	}
+
+	fn block_shape(block: &Block) -> String {
+		format!("{{ {} }}", block.statements().iter().map(statement_shape).collect::<Vec<_>>().join(" "))
+	}
+
+	fn if_shape(if_statement: &IfStatement) -> String {
+		let consequence = format!("if {} {}", shape(if_statement.condition()), block_shape(if_statement.consequence()));
+
+		match if_statement.alternative() {
+			Some(ElseBranch::If(else_if_statement)) => format!("{} else {}", consequence, if_shape(else_if_statement)),
+			Some(ElseBranch::Block(block)) => format!("{} else {}", consequence, block_shape(block)),
+			None => consequence
+		}
+	}
+
+	fn parse_shape(code: &str) -> (String, usize) {
+		let tokens = lex(code);
+		let parser = Parser::new(&tokens);
+		let (syntax, diagnostics) = parser.parse();
+
+		match syntax.as_ref() {
+			Syntax::Program(program) => {
+				(program.statements().iter().map(statement_shape).collect::<Vec<_>>().join(" "), diagnostics.len())
+			}
+		}
+	}
+
+	#[test]
+	fn every_operator_pair_groups_by_precedence() {
+		for first in BINARY_OPERATORS {
+			for second in BINARY_OPERATORS {
+				let code = format!("1 {} 2 {} 3", first.symbol(), second.symbol());
+				let (shape, diagnostic_count) = parse_shape(&code);
+
+				let expected_shape = if first.precedence() >= second.precedence() {
+					format!("((1 {} 2) {} 3)", first.symbol(), second.symbol())
+				} else {
+					format!("(1 {} (2 {} 3))", first.symbol(), second.symbol())
+				};
+
+				let chained = first.precedence() == second.precedence() && first.associativity() == Associativity::None;
+
+				assert_eq!(shape, expected_shape, "`{}`", code);
+				assert_eq!(diagnostic_count, chained as usize, "`{}`", code);
+			}
+		}
+	}
+
+	#[test]
+	fn prefix_operators_bind_tighter_than_binary_operators() {
+		for unary in UNARY_OPERATORS {
+			for binary in BINARY_OPERATORS {
+				let code = format!("{}1 {} 2", unary.symbol(), binary.symbol());
+				assert_eq!(parse_shape(&code), (format!("(({}1) {} 2)", unary.symbol(), binary.symbol()), 0), "`{}`", code);
+
+				let code = format!("1 {} {}2", binary.symbol(), unary.symbol());
+				assert_eq!(parse_shape(&code), (format!("(1 {} ({}2))", binary.symbol(), unary.symbol()), 0), "`{}`", code);
+			}
+		}
+	}
+
+	#[test]
+	fn same_precedence_operators_associate_to_the_left() {
+		assert_eq!(parse_shape("10 - 4 - 3"), (String::from("((10 - 4) - 3)"), 0));
+		assert_eq!(parse_shape("8 / 4 / 2 % 3"), (String::from("(((8 / 4) / 2) % 3)"), 0));
+		assert_eq!(parse_shape("1 || 2 || 3 && 4"), (String::from("((1 || 2) || (3 && 4))"), 0));
+	}
+
+	#[test]
+	fn prefix_operators_nest() {
+		assert_eq!(parse_shape("- -2 * 3"), (String::from("((-(-2)) * 3)"), 0));
+		assert_eq!(parse_shape("!!1 && 2"), (String::from("((!(!1)) && 2)"), 0));
+	}
+
+	#[test]
+	fn assignment_binds_loosest_and_associates_to_the_right() {
+		assert_eq!(parse_shape("a = b = 1 + 2 || c"), (String::from("(a = (b = ((1 + 2) || c)))"), 0));
+		assert_eq!(parse_shape("let a = b = -c"), (String::from("let a = (b = (-c));"), 0));
+		assert_eq!(parse_shape("1 + a = 2"), (String::from("<error>"), 1));
+	}
+
+	#[test]
+	fn statements_are_separated_by_semicolons() {
+		assert_eq!(parse_shape("let a = 1; { a; { b } } a + 1"), (String::from("let a = 1; { a; { b } } (a + 1)"), 0));
+		assert_eq!(parse_shape(";; 1;; 2"), (String::from("1; 2"), 0));
+		assert_eq!(parse_shape("1 2"), (String::from("1 2"), 1));
+	}
+
+	#[test]
+	fn control_flow_statements_need_no_semicolon() {
+		assert_eq!(
+			parse_shape("if a { 1 } else if b { 2 } else { 3 } while a < 3 { a = a + 1; continue } for i in 0..a + 1 { break; }"),
+			(String::from("if a { 1 } else if b { 2 } else { 3 } while (a < 3) { (a = (a + 1)); continue; } for i in (0 .. (a + 1)) { break; }"), 0)
+		);
+		assert_eq!(parse_shape("if a 1"), (String::from("if a {  }"), 1));
+	}
+
+	#[test]
+	fn loop_control_is_only_allowed_inside_loops() {
+		assert_eq!(parse_shape("while a { if b { break } }").1, 0);
+		assert_eq!(parse_shape("for i in a { { continue; } }").1, 0);
+		assert_eq!(parse_shape("break; { continue } if a { break }").1, 3);
+		assert_eq!(parse_shape("while a { } break").1, 1);
+	}
+
+	#[test]
+	fn calls_bind_tighter_than_prefix_operators() {
+		assert_eq!(parse_shape("-f(1, g(2) + 3)(x,) * h()"), (String::from("((-f(1, (g(2) + 3))(x)) * h())"), 0));
+		assert_eq!(parse_shape("(f)(1)"), (String::from("[f](1)"), 0));
+		assert_eq!(parse_shape("f(1 2)").1, 1);
+		assert_eq!(parse_shape("f(1,").1, 1);
+	}
+
+	#[test]
+	fn structs_are_declared_built_and_accessed() {
+		assert_eq!(
+			parse_shape("struct Point { x: Float, y: Float, } let p = Point { y: 2.0, x: -1.0 }; p.x = p.y + f(p).x.y"),
+			(String::from("struct Point { x: Float, y: Float } let p = Point { y: 2.0, x: (-1.0) }; (p.x = (p.y + f(p).x.y))"), 0)
+		);
+		assert_eq!(parse_shape("if p == (Point { x: 1 }) { p }"), (String::from("if (p == [Point { x: 1 }]) { p }"), 0));
+		assert_eq!(parse_shape("while a { b }"), (String::from("while a { b }"), 0));
+		assert_eq!(parse_shape("struct P { x Float } Point { x: 1 y: 2 }; f().x = 1").1, 3);
+	}
+
+	#[test]
+	fn arrays_are_built_indexed_and_sliced() {
+		assert_eq!(
+			parse_shape("let a: [[Integer]] = [[1, 2], [],]; a[0][1] = -a[1 + 1][0..2].len()"),
+			(String::from("let a: [[Integer]] = array(array(1, 2), array()); (a[0][1] = (-a[(1 + 1)][(0 .. 2)].len()))"), 0)
+		);
+		assert_eq!(parse_shape("[1, 2 3]; a[1; f()[0] = 1; let b: [Integer = c").1, 4);
+	}
+
+	#[test]
+	fn strings_interpolate_expressions() {
+		assert_eq!(
+			parse_shape(r#""a ${b + 1} c ${"d ${e}" + f(P { x: 1 })}""#),
+			(String::from(r#""a ${(b + 1)} c ${("d ${e}" + f(P { x: 1 }))}""#), 0)
+		);
+		assert_eq!(parse_shape(r#""${}"; "${a b}"; let c = "${(d}""#).1, 3);
+	}
+
+	#[test]
+	fn functions_are_declared_with_parameters_and_return() {
+		assert_eq!(
+			parse_shape("fn f(a, b,) { if a { return b } return; } fn g() { 1 }"),
+			(String::from("fn f(a, b) { if a { return b; } return; } fn g() { 1 }"), 0)
+		);
+		assert_eq!(parse_shape("return 1; fn f() { while a { fn g() { break } } }").1, 2);
+		assert_eq!(parse_shape("fn f(1) { }").1, 1);
+	}
+
+	#[test]
+	fn doc_comments_are_attached_to_declarations() {
+		let tokens = lex("/// Adds.\n/// Twice.\nfn f() { } // no\n//// nor this\nstruct P { } /// Answer\nlet a = 1; /// Dangling\na");
+		let parser = Parser::new(&tokens);
+		let (syntax, diagnostics) = parser.parse();
+
+		let documentation = match syntax.as_ref() {
+			Syntax::Program(program) => program.statements().iter()
+				.map(|statement| match statement {
+					Statement::Function(function_declaration) => function_declaration.documentation(),
+					Statement::Struct(struct_declaration) => struct_declaration.documentation(),
+					Statement::VariableDeclaration(variable_declaration) => variable_declaration.documentation(),
+					_ => &[]
+				})
+				.collect::<Vec<_>>()
+		};
+
+		assert!(diagnostics.is_empty());
+		assert_eq!(documentation, vec![&[" Adds.", " Twice."][..], &[], &[" Answer"], &[]]);
+	}
+
+	#[test]
+	fn keywords_are_reported_but_kept_as_names() {
+		assert_eq!(parse_shape("let if = 1; fn while(for) { } let iffy = true"), (String::from("let if = 1; fn while(for) {  } let iffy = true;"), 3));
+		assert_eq!(parse_shape("for in in 0..1 { }"), (String::from("for in in (0 .. 1) {  }"), 1));
+		assert_eq!(parse_shape("let x = else").1, 1);
+	}
+
+	#[test]
+	fn names_take_optional_type_annotations() {
+		assert_eq!(
+			parse_shape("let a: Integer = 1; fn f(b: Float, c) -> Boolean { b > c } fn g() -> String { }"),
+			(String::from("let a: Integer = 1; fn f(b: Float, c) -> Boolean { (b > c) } fn g() -> String {  }"), 0)
+		);
+		assert_eq!(parse_shape("let a: = 1; fn f() -> { }").1, 2);
+	}
+
+	#[test]
+	fn every_error_is_reported() {
+		let (_, diagnostic_count) = parse_shape("let = 1; (1 + ); 2 * ; { 3 4 } let x 5; )");
+		assert_eq!(diagnostic_count, 6);
+	}
+
+	#[test]
+	fn parentheses_override_precedence() {
+		assert_eq!(parse_shape("(1 + 2) * 3"), (String::from("([(1 + 2)] * 3)"), 0));
+		assert_eq!(parse_shape("-(2 + 3)"), (String::from("(-[(2 + 3)])"), 0));
+		assert_eq!(parse_shape("1 < (2 < 3)"), (String::from("(1 < [(2 < 3)])"), 0));
+	}
+
+	#[test]
+	fn every_syntax_error_is_reported_and_parsing_resumes_after_it() {
+		let code = "f(1 + ) + 2; let = 3; g(4, 5); (6 + ; h(7)";
+		let tokens = lex(code);
+		let (_, diagnostics) = Parser::new(&tokens).parse();
+		let errors = diagnostics.iter()
+			.map(|diagnostic| (diagnostic.code(), &code[diagnostic.primary_label().span().start()..diagnostic.primary_label().span().end()]))
+			.collect::<Vec<_>>();
 
-		Some(Expression::Binary(BinaryExpression::new(Rc::new(left_operand), Rc::new(right_operand), binary_expression_kind)))
+		// The call is closed at the `)` and the `let` is given up at the `;`, what follows each parses as usual.
+		assert_eq!(errors, vec![("E0100", ")"), ("E0107", "="), ("E0100", ";"), ("E0102", ";")]);
+		assert_eq!(parse_shape(code).0, "(f((1 + <error>)) + 2); <error>; g(4, 5); [(6 + <error>)]; h(7)");
 	}
 }
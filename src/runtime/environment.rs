@@ -0,0 +1,98 @@
+use std::rc::Rc;
+use std::collections::HashMap;
+use std::iter;
+use crate::syntax::StructDeclaration;
+use crate::runtime::Value;
+
+// Structs are named apart from variables, so a struct and a variable can share a name.
+#[derive(Clone)]
+struct Scope<'a> {
+	variables: HashMap<String, Value<'a>>,
+	structs: HashMap<String, Rc<StructDeclaration<'a>>>
+}
+
+impl<'a> Scope<'a> {
+	fn new() -> Self {
+		Self {
+			variables: HashMap::new(),
+			structs: HashMap::new()
+		}
+	}
+}
+
+// Variables live in a stack of scopes, names are looked up from the innermost scope outwards.
+// Every function call starts a frame of its own, which only sees its own scopes and the outermost, global one.
+#[derive(Clone)]
+pub struct Environment<'a> {
+	scopes: Vec<Scope<'a>>,
+	// Index of the first scope of every active call.
+	frames: Vec<usize>
+}
+
+impl<'a> Environment<'a> {
+	pub fn new() -> Self {
+		Self {
+			scopes: vec![Scope::new()],
+			frames: Vec::new()
+		}
+	}
+
+	pub fn push_scope(&mut self) {
+		self.scopes.push(Scope::new());
+	}
+
+	pub fn pop_scope(&mut self) {
+		self.scopes.pop();
+	}
+
+	pub fn push_frame(&mut self) {
+		self.frames.push(self.scopes.len());
+		self.push_scope();
+	}
+
+	pub fn pop_frame(&mut self) {
+		if let Some(start) = self.frames.pop() {
+			self.scopes.truncate(start);
+		}
+	}
+
+	// How many calls are active.
+	pub fn depth(&self) -> usize {
+		self.frames.len()
+	}
+
+	// Declaring a name again shadows the previous variable, even within the same scope.
+	pub fn define(&mut self, name: &str, value: Value<'a>) {
+		if let Some(scope) = self.scopes.last_mut() {
+			scope.variables.insert(name.to_string(), value);
+		}
+	}
+
+	pub fn get(&self, name: &str) -> Option<&Value<'a>> {
+		self.visible_scopes().find_map(|index| self.scopes[index].variables.get(name))
+	}
+
+	pub fn define_struct(&mut self, declaration: Rc<StructDeclaration<'a>>) {
+		if let Some(scope) = self.scopes.last_mut() {
+			scope.structs.insert(declaration.name().to_string(), declaration);
+		}
+	}
+
+	pub fn get_struct(&self, name: &str) -> Option<&Rc<StructDeclaration<'a>>> {
+		self.visible_scopes().find_map(|index| self.scopes[index].structs.get(name))
+	}
+
+	// The innermost variable with the given name, to be changed in place.
+	pub fn get_mut(&mut self, name: &str) -> Option<&mut Value<'a>> {
+		let index = self.visible_scopes().find(|&index| self.scopes[index].variables.contains_key(name))?;
+
+		self.scopes[index].variables.get_mut(name)
+	}
+
+	// Indices of the scopes visible from the current frame, innermost first.
+	fn visible_scopes(&self) -> impl Iterator<Item = usize> {
+		let start = self.frames.last().copied().unwrap_or(1);
+
+		(start..self.scopes.len()).rev().chain(iter::once(0))
+	}
+}
@@ -0,0 +1,192 @@
+use crate::diagnostic::{
+	Diagnostic,
+	Span,
+	pluralise,
+	quote_list
+};
+use crate::syntax::{
+	BinaryExpressionKind,
+	UnaryExpressionKind
+};
+use crate::runtime::ValueType;
+
+pub enum RuntimeErrorKind {
+	InvalidBinaryOperands {
+		operator: BinaryExpressionKind,
+		left_operand: (ValueType, Span),
+		right_operand: (ValueType, Span)
+	},
+	InvalidUnaryOperand {
+		operator: UnaryExpressionKind,
+		operand: (ValueType, Span)
+	},
+	UnexpectedType {
+		expected: ValueType,
+		found: ValueType
+	},
+	IntegerOverflow {
+		operator: &'static str
+	},
+	DivisionByZero {
+		divisor: Span
+	},
+	UndefinedVariable {
+		name: String
+	},
+	NotIterable {
+		found: ValueType
+	},
+	NotCallable {
+		found: ValueType
+	},
+	ArityMismatch {
+		name: String,
+		expected: usize,
+		found: usize,
+		// Where the function is declared, the methods of arrays are not.
+		definition: Option<Span>
+	},
+	CallDepthExceeded {
+		limit: usize
+	},
+	UndefinedStruct {
+		name: String
+	},
+	NoSuchField {
+		field: String,
+		found: String
+	},
+	MissingFields {
+		name: String,
+		fields: Vec<String>
+	},
+	NotIndexable {
+		found: ValueType
+	},
+	IndexOutOfBounds {
+		index: i64,
+		length: usize
+	},
+	SliceOutOfBounds {
+		start: i64,
+		end: i64,
+		length: usize
+	},
+	EmptyArray,
+	SyntaxError
+}
+
+pub struct RuntimeError {
+	kind: RuntimeErrorKind,
+	span: Span
+}
+
+impl RuntimeError {
+	pub fn new(kind: RuntimeErrorKind, span: Span) -> Self {
+		Self {
+			kind,
+			span
+		}
+	}
+
+	pub fn to_diagnostic(&self) -> Diagnostic {
+		match &self.kind {
+			RuntimeErrorKind::InvalidBinaryOperands { operator, left_operand, right_operand } => {
+				Diagnostic::error(
+					"E0200",
+					format!("cannot apply `{}` to `{}` and `{}`", operator.symbol(), left_operand.0, right_operand.0),
+					self.span
+				)
+					.with_label(left_operand.1, format!("this is of type `{}`", left_operand.0))
+					.with_label(right_operand.1, format!("this is of type `{}`", right_operand.0))
+			},
+			RuntimeErrorKind::InvalidUnaryOperand { operator, operand } => {
+				Diagnostic::error("E0201", format!("cannot apply unary `{}` to `{}`", operator.symbol(), operand.0), self.span)
+					.with_label(operand.1, format!("this is of type `{}`", operand.0))
+			},
+			RuntimeErrorKind::UnexpectedType { expected, found } => {
+				Diagnostic::error("E0205", format!("expected `{}`, found `{}`", expected, found), self.span)
+					.with_primary_label(format!("this is of type `{}`", found))
+			},
+			RuntimeErrorKind::IntegerOverflow { operator } => {
+				Diagnostic::error("E0203", format!("integer overflow in `{}`", operator), self.span)
+					.with_primary_label("the result does not fit in an `Integer`")
+					.with_note(format!("integers range from {} to {}", i64::MIN, i64::MAX))
+			},
+			RuntimeErrorKind::DivisionByZero { divisor } => {
+				Diagnostic::error("E0204", "integer division by zero", self.span)
+					.with_label(*divisor, "this is zero")
+			},
+			RuntimeErrorKind::UndefinedVariable { name } => {
+				Diagnostic::error("E0206", format!("cannot find variable `{}`", name), self.span)
+					.with_primary_label("not declared in this scope")
+					.with_note(format!("declare it first with `let {} = ...`", name))
+			},
+			RuntimeErrorKind::NotIterable { found } => {
+				Diagnostic::error("E0207", format!("cannot iterate over `{}`", found), self.span)
+					.with_primary_label(format!("this is of type `{}`", found))
+					.with_note("`for` loops go over a `Range` such as `0..10`, the characters of a `String` or the elements of an array")
+			},
+			RuntimeErrorKind::NotCallable { found } => {
+				Diagnostic::error("E0208", format!("cannot call `{}`", found), self.span)
+					.with_primary_label(format!("this is of type `{}`", found))
+					.with_note("only functions can be called")
+			},
+			RuntimeErrorKind::ArityMismatch { name, expected, found, definition } => {
+				let diagnostic = Diagnostic::error(
+					"E0209",
+					format!("function `{}` takes {} but {} {} given", name, pluralise(*expected, "argument"), found, if *found == 1 { "was" } else { "were" }),
+					self.span
+				)
+					.with_primary_label(format!("called with {}", pluralise(*found, "argument")));
+
+				match definition {
+					Some(definition) => diagnostic.with_label(*definition, format!("defined here with {}", pluralise(*expected, "parameter"))),
+					None => diagnostic
+				}
+			},
+			RuntimeErrorKind::CallDepthExceeded { limit } => {
+				Diagnostic::error("E0210", "maximum call depth exceeded", self.span)
+					.with_primary_label(format!("this call is nested more than {} calls deep", limit))
+					.with_note("check that the recursion reaches a case that does not call itself again")
+			},
+			RuntimeErrorKind::UndefinedStruct { name } => {
+				Diagnostic::error("E0211", format!("cannot find struct `{}`", name), self.span)
+					.with_primary_label("not declared in this scope")
+			},
+			RuntimeErrorKind::NoSuchField { field, found } => {
+				Diagnostic::error("E0212", format!("no field `{}` on `{}`", field, found), self.span)
+					.with_primary_label("unknown field")
+			},
+			RuntimeErrorKind::MissingFields { name, fields } => {
+				Diagnostic::error(
+					"E0213",
+					format!("missing {} {} in `{}`", if fields.len() == 1 { "field" } else { "fields" }, quote_list(fields), name),
+					self.span
+				)
+					.with_primary_label(format!("{} not given", pluralise(fields.len(), "field")))
+			},
+			RuntimeErrorKind::NotIndexable { found } => {
+				Diagnostic::error("E0214", format!("cannot index into `{}`", found), self.span)
+					.with_primary_label(format!("this is of type `{}`", found))
+					.with_note("only arrays can be indexed")
+			},
+			RuntimeErrorKind::IndexOutOfBounds { index, length } => {
+				Diagnostic::error("E0215", format!("index out of bounds: the length is {} but the index is {}", length, index), self.span)
+					.with_primary_label("this index is out of bounds")
+			},
+			RuntimeErrorKind::SliceOutOfBounds { start, end, length } => {
+				Diagnostic::error("E0216", format!("slice out of bounds: the length is {} but the range is {}..{}", length, start, end), self.span)
+					.with_primary_label("this range is out of bounds")
+					.with_note(format!("a slice can start and end anywhere from 0 to {}", length))
+			},
+			RuntimeErrorKind::EmptyArray => {
+				Diagnostic::error("E0217", "cannot pop from an empty array", self.span)
+					.with_primary_label("the array is empty here")
+			},
+			RuntimeErrorKind::SyntaxError => {
+				Diagnostic::error("E0202", "cannot evaluate code that failed to parse", self.span)
+			}
+		}
+	}
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use crate::syntax::{
 	Expression,
 	BinaryExpression,
@@ -6,44 +7,733 @@ use crate::syntax::{
 	UnaryExpressionKind,
 	ParenthesisedExpression,
 	LiteralExpression,
-	LiteralExpressionKind
+	LiteralExpressionKind,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	StringPart,
+	InterpolatedStringExpression
 };
+use crate::runtime::{
+	Environment,
+	Value,
+	Function,
+	Structure,
+	ValueType,
+	RuntimeErrorKind,
+	RuntimeError
+};
+use crate::runtime::evaluation::statement::{
+	Flow,
+	evaluate_statements
+};
+use crate::runtime::evaluation::place::{
+	PlacePath,
+	Step,
+	Place,
+	Position,
+	position
+};
+use crate::lexing::TokenValue;
 
-pub fn evaluate_expression(expression: &Expression) -> f64 {
+// Deeper recursion is reported as an error rather than overflowing the stack of the interpreter itself.
+const MAXIMUM_CALL_DEPTH: usize = 4096;
+
+pub fn evaluate_expression<'a>(expression: &Expression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
 	match expression {
-		Expression::Binary(binary_expression) => evaluate_binary_expression(binary_expression),
-		Expression::Unary(unary_expression) => evaluate_unary_expression(unary_expression),
-		Expression::Parenthesised(parenthesised_expression) => evaluate_parenthesised_expression(parenthesised_expression),
-		Expression::Literal(literal_expression) => evaluate_literal_expression(literal_expression)
+		Expression::Binary(binary_expression) => evaluate_binary_expression(binary_expression, environment),
+		Expression::Unary(unary_expression) => evaluate_unary_expression(unary_expression, environment),
+		Expression::Parenthesised(parenthesised_expression) => evaluate_parenthesised_expression(parenthesised_expression, environment),
+		Expression::Literal(literal_expression) => evaluate_literal_expression(literal_expression),
+		Expression::Identifier(identifier_expression) => evaluate_identifier_expression(identifier_expression, environment),
+		Expression::Assignment(assignment_expression) => evaluate_assignment_expression(assignment_expression, environment),
+		Expression::Call(call_expression) => evaluate_call_expression(call_expression, environment),
+		Expression::StructLiteral(struct_literal_expression) => evaluate_struct_literal_expression(struct_literal_expression, environment),
+		Expression::FieldAccess(field_access_expression) => evaluate_field_access_expression(field_access_expression, environment),
+		Expression::ArrayLiteral(array_literal_expression) => evaluate_array_literal_expression(array_literal_expression, environment),
+		Expression::Index(index_expression) => evaluate_index_expression(index_expression, environment),
+		Expression::InterpolatedString(interpolated_string_expression) => evaluate_interpolated_string_expression(interpolated_string_expression, environment),
+		Expression::Error(error_expression) => Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, error_expression.span()))
+	}
+}
+
+fn evaluate_binary_expression<'a>(binary_expression: &BinaryExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	if matches!(binary_expression.kind(), BinaryExpressionKind::LogicalAnd | BinaryExpressionKind::LogicalOr) {
+		return evaluate_logical_expression(binary_expression, environment);
+	}
+
+	let left_operand = evaluate_expression(binary_expression.left_operand(), environment)?;
+	let right_operand = evaluate_expression(binary_expression.right_operand(), environment)?;
+
+	let result = match binary_expression.kind() {
+		BinaryExpressionKind::Equality |
+		BinaryExpressionKind::NotEquality => evaluate_equality(binary_expression.kind(), &left_operand, &right_operand),
+
+		BinaryExpressionKind::LessThan |
+		BinaryExpressionKind::GreaterThan |
+		BinaryExpressionKind::LessThanEqualTo |
+		BinaryExpressionKind::GreaterThanEqualTo => evaluate_comparison(binary_expression.kind(), &left_operand, &right_operand),
+
+		BinaryExpressionKind::Range => evaluate_range(&left_operand, &right_operand),
+
+		_ => evaluate_arithmetic(binary_expression, &left_operand, &right_operand)?
+	};
+
+	result.ok_or_else(|| RuntimeError::new(
+		RuntimeErrorKind::InvalidBinaryOperands {
+			operator: binary_expression.kind(),
+			left_operand: (left_operand.value_type(), binary_expression.left_operand().span()),
+			right_operand: (right_operand.value_type(), binary_expression.right_operand().span())
+		},
+		binary_expression.operator().span()
+	))
+}
+
+// `&&` and `||` only evaluate their right operand when the left one does not decide the result.
+fn evaluate_logical_expression<'a>(binary_expression: &BinaryExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let short_circuit = binary_expression.kind() == BinaryExpressionKind::LogicalOr;
+
+	if expect_boolean(binary_expression.left_operand(), environment)? == short_circuit {
+		return Ok(Value::Boolean(short_circuit));
+	}
+
+	Ok(Value::Boolean(expect_boolean(binary_expression.right_operand(), environment)?))
+}
+
+pub fn expect_boolean<'a>(expression: &Expression<'a>, environment: &mut Environment<'a>) -> Result<bool, RuntimeError> {
+	match evaluate_expression(expression, environment)? {
+		Value::Boolean(boolean) => Ok(boolean),
+		value => Err(RuntimeError::new(
+			RuntimeErrorKind::UnexpectedType {
+				expected: ValueType::Boolean,
+				found: value.value_type()
+			},
+			expression.span()
+		))
+	}
+}
+
+// Values of the same type can be compared for equality, integers and floats compare as floats.
+fn evaluate_equality<'a>(kind: BinaryExpressionKind, left_operand: &Value<'a>, right_operand: &Value<'a>) -> Option<Value<'a>> {
+	let equal = match (as_float(left_operand), as_float(right_operand)) {
+		(Some(left_float), Some(right_float)) if left_operand.value_type() != right_operand.value_type() => left_float == right_float,
+		_ if left_operand.value_type() == right_operand.value_type() => left_operand == right_operand,
+		_ => return None
+	};
+
+	Some(Value::Boolean(equal == (kind == BinaryExpressionKind::Equality)))
+}
+
+// Numbers, characters and strings are ordered, comparisons involving `NaN` are always false.
+fn evaluate_comparison<'a>(kind: BinaryExpressionKind, left_operand: &Value<'a>, right_operand: &Value<'a>) -> Option<Value<'a>> {
+	let ordering = match (left_operand, right_operand) {
+		(Value::Integer(left_integer), Value::Integer(right_integer)) => Some(left_integer.cmp(right_integer)),
+		(Value::Character(left_character), Value::Character(right_character)) => Some(left_character.cmp(right_character)),
+		(Value::String(left_string), Value::String(right_string)) => Some(left_string.cmp(right_string)),
+		_ => match (as_float(left_operand), as_float(right_operand)) {
+			(Some(left_float), Some(right_float)) => left_float.partial_cmp(&right_float),
+			_ => return None
+		}
+	};
+
+	Some(Value::Boolean(match kind {
+		BinaryExpressionKind::LessThan => ordering == Some(Ordering::Less),
+		BinaryExpressionKind::GreaterThan => ordering == Some(Ordering::Greater),
+		BinaryExpressionKind::LessThanEqualTo => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+		_ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+	}))
+}
+
+// Ranges are made of integers, a range whose end is not past its start is empty.
+fn evaluate_range<'a>(left_operand: &Value<'a>, right_operand: &Value<'a>) -> Option<Value<'a>> {
+	match (left_operand, right_operand) {
+		(Value::Integer(start), Value::Integer(end)) => Some(Value::Range(*start, *end)),
+		_ => None
+	}
+}
+
+// Arithmetic between two integers stays integral, division truncates towards zero and overflowing is an error.
+// As soon as one operand is a float the other one is promoted to a float as well.
+fn evaluate_arithmetic<'a>(binary_expression: &BinaryExpression<'a>, left_operand: &Value<'a>, right_operand: &Value<'a>) -> Result<Option<Value<'a>>, RuntimeError> {
+	Ok(match (binary_expression.kind(), left_operand, right_operand) {
+		(BinaryExpressionKind::Addition, Value::String(left_string), Value::String(right_string)) => {
+			Some(Value::String(format!("{}{}", left_string, right_string)))
+		},
+		(kind, Value::Integer(left_integer), Value::Integer(right_integer)) => {
+			Some(evaluate_integer_arithmetic(binary_expression, kind, *left_integer, *right_integer)?)
+		},
+		(kind, _, _) => match (as_float(left_operand), as_float(right_operand)) {
+			(Some(left_float), Some(right_float)) => Some(evaluate_float_arithmetic(kind, left_float, right_float)),
+			_ => None
+		}
+	})
+}
+
+fn evaluate_integer_arithmetic<'a>(binary_expression: &BinaryExpression<'a>, kind: BinaryExpressionKind, left_operand: i64, right_operand: i64) -> Result<Value<'a>, RuntimeError> {
+	let operator_span = binary_expression.operator().span();
+
+	if right_operand == 0 && matches!(kind, BinaryExpressionKind::Division | BinaryExpressionKind::Modulo) {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::DivisionByZero {
+				divisor: binary_expression.right_operand().span()
+			},
+			operator_span
+		));
+	}
+
+	let result = match kind {
+		BinaryExpressionKind::Addition => left_operand.checked_add(right_operand),
+		BinaryExpressionKind::Substraction => left_operand.checked_sub(right_operand),
+		BinaryExpressionKind::Multiplication => left_operand.checked_mul(right_operand),
+		BinaryExpressionKind::Division => left_operand.checked_div(right_operand),
+		_ => left_operand.checked_rem(right_operand)
+	};
+
+	result.map(Value::Integer).ok_or_else(|| RuntimeError::new(
+		RuntimeErrorKind::IntegerOverflow {
+			operator: kind.symbol()
+		},
+		operator_span
+	))
+}
+
+fn evaluate_float_arithmetic<'a>(kind: BinaryExpressionKind, left_operand: f64, right_operand: f64) -> Value<'a> {
+	Value::Float(match kind {
+		BinaryExpressionKind::Addition => left_operand + right_operand,
+		BinaryExpressionKind::Substraction => left_operand - right_operand,
+		BinaryExpressionKind::Multiplication => left_operand * right_operand,
+		BinaryExpressionKind::Division => left_operand / right_operand,
+		_ => left_operand % right_operand
+	})
+}
+
+// Integers take part in floating point arithmetic as floats.
+fn as_float(value: &Value) -> Option<f64> {
+	match value {
+		Value::Integer(integer) => Some(*integer as f64),
+		Value::Float(float) => Some(*float),
+		_ => None
+	}
+}
+
+fn evaluate_unary_expression<'a>(unary_expression: &UnaryExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let operand = evaluate_expression(unary_expression.operand(), environment)?;
+
+	match (unary_expression.kind(), operand) {
+		(UnaryExpressionKind::Identity, operand @ (Value::Integer(_) | Value::Float(_))) => Ok(operand),
+		(UnaryExpressionKind::Negation, Value::Integer(integer)) => integer.checked_neg().map(Value::Integer).ok_or_else(|| RuntimeError::new(
+			RuntimeErrorKind::IntegerOverflow {
+				operator: UnaryExpressionKind::Negation.symbol()
+			},
+			unary_expression.operator().span()
+		)),
+		(UnaryExpressionKind::Negation, Value::Float(float)) => Ok(Value::Float(- float)),
+		(UnaryExpressionKind::LogicalNot, Value::Boolean(boolean)) => Ok(Value::Boolean(!boolean)),
+		(kind, operand) => Err(RuntimeError::new(
+			RuntimeErrorKind::InvalidUnaryOperand {
+				operator: kind,
+				operand: (operand.value_type(), unary_expression.operand().span())
+			},
+			unary_expression.operator().span()
+		))
+	}
+}
+
+fn evaluate_parenthesised_expression<'a>(parenthesised_expression: &ParenthesisedExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	evaluate_expression(parenthesised_expression.content(), environment)
+}
+
+fn evaluate_identifier_expression<'a>(identifier_expression: &IdentifierExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	environment.get(identifier_expression.name()).cloned().ok_or_else(|| undefined_variable(identifier_expression))
+}
+
+// Assignment evaluates to the assigned value.
+fn evaluate_assignment_expression<'a>(assignment_expression: &AssignmentExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let value = evaluate_expression(assignment_expression.value(), environment)?;
+	assign(assignment_expression.target(), value.clone(), environment)?;
+
+	Ok(value)
+}
+
+// Fields and elements are changed where they are stored rather than in a copy of the struct or array holding them.
+fn assign<'a>(target: &Expression<'a>, value: Value<'a>, environment: &mut Environment<'a>) -> Result<(), RuntimeError> {
+	PlacePath::new(target, environment)?.resolve(environment)?.set(value, target)
+}
+
+// The fields are stored in the order they are declared in, whatever order they are given in.
+fn evaluate_struct_literal_expression<'a>(struct_literal_expression: &StructLiteralExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let Some(declaration) = environment.get_struct(struct_literal_expression.name()).cloned() else {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::UndefinedStruct {
+				name: struct_literal_expression.name().to_string()
+			},
+			struct_literal_expression.name_span()
+		));
+	};
+
+	let mut fields = vec![None; declaration.fields().len()];
+
+	for field in struct_literal_expression.fields() {
+		let value = evaluate_expression(field.value(), environment)?;
+
+		match declaration.fields().iter().position(|declared| declared.name() == field.name()) {
+			Some(index) => fields[index] = Some(value),
+			None => return Err(RuntimeError::new(
+				RuntimeErrorKind::NoSuchField {
+					field: field.name().to_string(),
+					found: declaration.name().to_string()
+				},
+				field.span()
+			))
+		}
+	}
+
+	let missing = declaration.fields().iter()
+		.zip(&fields)
+		.filter(|(_, value)| value.is_none())
+		.map(|(field, _)| field.name().to_string())
+		.collect::<Vec<_>>();
+
+	if !missing.is_empty() {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::MissingFields {
+				name: declaration.name().to_string(),
+				fields: missing
+			},
+			struct_literal_expression.span()
+		));
+	}
+
+	Ok(Value::Struct(Structure::new(declaration, fields.into_iter().flatten().collect())))
+}
+
+// A field of a variable is read where it is stored, without copying the rest of the struct.
+fn evaluate_field_access_expression<'a>(field_access_expression: &FieldAccessExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	if field_access_expression.object().is_assignable() {
+		let mut path = PlacePath::new(field_access_expression.object(), environment)?;
+		path.push(Step::Field(field_access_expression));
+
+		return Ok(path.resolve(environment)?.value());
+	}
+
+	let object = evaluate_expression(field_access_expression.object(), environment)?;
+
+	field_value(field_access_expression, object)
+}
+
+fn field_value<'a>(field_access_expression: &FieldAccessExpression<'a>, object: Value<'a>) -> Result<Value<'a>, RuntimeError> {
+	match &object {
+		Value::Struct(structure) => structure.field(field_access_expression.field()).cloned(),
+		_ => None
+	}.ok_or_else(|| no_such_field(field_access_expression, &object))
+}
+
+pub fn no_such_field(field_access_expression: &FieldAccessExpression, object: &Value) -> RuntimeError {
+	let found = match object {
+		Value::Struct(structure) => structure.declaration().name().to_string(),
+		_ => object.value_type().to_string()
+	};
+
+	RuntimeError::new(
+		RuntimeErrorKind::NoSuchField {
+			field: field_access_expression.field().to_string(),
+			found
+		},
+		field_access_expression.field_span()
+	)
+}
+
+// Arguments are evaluated from left to right in the caller's frame, the body then runs in a new frame
+// holding the parameters and the function itself, so it can call itself even when declared in a block.
+fn evaluate_call_expression<'a>(call_expression: &CallExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let callee = match call_expression.callee() {
+		Expression::FieldAccess(field_access_expression) if field_access_expression.object().is_assignable() => {
+			let path = PlacePath::new(field_access_expression.object(), environment)?;
+
+			let place = path.resolve(environment)?;
+
+			if place.holds_array() {
+				return evaluate_array_method(call_expression, field_access_expression, Receiver::Place(path), environment);
+			}
+
+			place.field(field_access_expression)?.value()
+		},
+		Expression::FieldAccess(field_access_expression) => match evaluate_expression(field_access_expression.object(), environment)? {
+			Value::Array(elements) => return evaluate_array_method(call_expression, field_access_expression, Receiver::Temporary(elements), environment),
+			object => field_value(field_access_expression, object)?
+		},
+		callee => evaluate_expression(callee, environment)?
+	};
+
+	let function = match callee {
+		Value::Function(function) => function,
+		value => return Err(RuntimeError::new(
+			RuntimeErrorKind::NotCallable {
+				found: value.value_type()
+			},
+			call_expression.callee().span()
+		))
+	};
+
+	let declaration = function.declaration();
+
+	if declaration.parameters().len() != call_expression.arguments().len() {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::ArityMismatch {
+				name: declaration.name().to_string(),
+				expected: declaration.parameters().len(),
+				found: call_expression.arguments().len(),
+				definition: Some(declaration.signature())
+			},
+			call_expression.span()
+		));
+	}
+
+	if environment.depth() >= MAXIMUM_CALL_DEPTH {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::CallDepthExceeded {
+				limit: MAXIMUM_CALL_DEPTH
+			},
+			call_expression.span()
+		));
+	}
+
+	let mut arguments = Vec::new();
+
+	for argument in call_expression.arguments() {
+		arguments.push(evaluate_expression(argument, environment)?);
+	}
+
+	environment.push_frame();
+	environment.define(declaration.name(), Value::Function(Function::clone(&function)));
+
+	for (parameter, argument) in declaration.parameters().iter().zip(arguments) {
+		environment.define(parameter.name(), argument);
+	}
+
+	let flow = evaluate_statements(declaration.body().statements(), environment);
+	environment.pop_frame();
+
+	// Without a `return` the function evaluates to its body's final expression, if there is one.
+	Ok(match flow? {
+		Flow::Return(value) |
+		Flow::Normal(Some(value)) => value,
+		_ => Value::Unit
+	})
+}
+
+// The array a method is called on.
+enum Receiver<'p, 'a> {
+	// An array stored in a variable, which the method changes in place.
+	Place(PlacePath<'p, 'a>),
+	// An array a call or a literal evaluated to, only the result of the method is left of it.
+	Temporary(Vec<Value<'a>>)
+}
+
+// `len`, `push` and `pop`, the last two change the array where it is stored.
+fn evaluate_array_method<'a>(
+	call_expression: &CallExpression<'a>,
+	field_access_expression: &FieldAccessExpression<'a>,
+	receiver: Receiver<'_, 'a>,
+	environment: &mut Environment<'a>
+) -> Result<Value<'a>, RuntimeError> {
+	let method = field_access_expression.field();
+
+	let parameter_count = match method {
+		"len" | "pop" => 0,
+		"push" => 1,
+		_ => return Err(no_such_field(field_access_expression, &Value::Array(Vec::new())))
+	};
+
+	if call_expression.arguments().len() != parameter_count {
+		return Err(RuntimeError::new(
+			RuntimeErrorKind::ArityMismatch {
+				name: method.to_string(),
+				expected: parameter_count,
+				found: call_expression.arguments().len(),
+				definition: None
+			},
+			call_expression.span()
+		));
+	}
+
+	let mut arguments = Vec::new();
+
+	for argument in call_expression.arguments() {
+		arguments.push(evaluate_expression(argument, environment)?);
+	}
+
+	// The path is only followed now, as the arguments can have changed what it leads to.
+	// A slice is a new array, so a method called on one leaves the array it was taken from alone.
+	let mut temporary;
+
+	let place = match receiver {
+		Receiver::Place(path) => match path.resolve(environment)? {
+			place @ Place::Value(_) => place,
+			place => {
+				temporary = place.value();
+				Place::Value(&mut temporary)
+			}
+		},
+		Receiver::Temporary(elements) => {
+			temporary = Value::Array(elements);
+			Place::Value(&mut temporary)
+		}
+	};
+
+	let elements = place.elements().map_err(|place| no_such_field(field_access_expression, &place.value()))?;
+
+	Ok(match method {
+		"len" => Value::Integer(elements.len() as i64),
+		"push" => {
+			elements.extend(arguments);
+			Value::Unit
+		},
+		_ => elements.pop().ok_or_else(|| RuntimeError::new(RuntimeErrorKind::EmptyArray, call_expression.span()))?
+	})
+}
+
+fn evaluate_array_literal_expression<'a>(array_literal_expression: &ArrayLiteralExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let mut elements = Vec::new();
+
+	for element in array_literal_expression.elements() {
+		elements.push(evaluate_expression(element, environment)?);
+	}
+
+	Ok(Value::Array(elements))
+}
+
+// Elements of a variable are read where they are stored, only an array that is not stored anywhere is taken apart.
+fn evaluate_index_expression<'a>(index_expression: &IndexExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	if index_expression.object().is_assignable() {
+		let mut path = PlacePath::new(index_expression.object(), environment)?;
+		let index = evaluate_expression(index_expression.index(), environment)?;
+		path.push(Step::Index(index_expression, index));
+
+		return Ok(path.resolve(environment)?.value());
 	}
+
+	let object = evaluate_expression(index_expression.object(), environment)?;
+	let index = evaluate_expression(index_expression.index(), environment)?;
+	let mut elements = expect_array(index_expression, object)?;
+
+	Ok(match position(index_expression, index, elements.len())? {
+		Position::Element(position) => elements.swap_remove(position),
+		Position::Slice(range) => Value::Array(elements.drain(range).collect())
+	})
 }
 
-fn evaluate_binary_expression(binary_expression: &BinaryExpression) -> f64 {
-	match binary_expression.kind() {
-		BinaryExpressionKind::Addition => evaluate_expression(binary_expression.left_operand()) + evaluate_expression(binary_expression.right_operand()),
-		BinaryExpressionKind::Substraction => evaluate_expression(binary_expression.left_operand()) - evaluate_expression(binary_expression.right_operand()),
-		BinaryExpressionKind::Multiplication => evaluate_expression(binary_expression.left_operand()) * evaluate_expression(binary_expression.right_operand()),
-		BinaryExpressionKind::Division => evaluate_expression(binary_expression.left_operand()) / evaluate_expression(binary_expression.right_operand()),
-		BinaryExpressionKind::Modulo => evaluate_expression(binary_expression.left_operand()) % evaluate_expression(binary_expression.right_operand())
+fn expect_array<'a>(index_expression: &IndexExpression<'a>, object: Value<'a>) -> Result<Vec<Value<'a>>, RuntimeError> {
+	match object {
+		Value::Array(elements) => Ok(elements),
+		object => Err(RuntimeError::new(
+			RuntimeErrorKind::NotIndexable {
+				found: object.value_type()
+			},
+			index_expression.object().span()
+		))
 	}
 }
 
-fn evaluate_unary_expression(unary_expression: &UnaryExpression) -> f64 {
-	match unary_expression.kind() {
-		UnaryExpressionKind::Identity => evaluate_expression(unary_expression.operand()),
-		UnaryExpressionKind::Negation => - evaluate_expression(unary_expression.operand())
+// Every value is formatted the way it is printed.
+fn evaluate_interpolated_string_expression<'a>(interpolated_string_expression: &InterpolatedStringExpression<'a>, environment: &mut Environment<'a>) -> Result<Value<'a>, RuntimeError> {
+	let mut string = String::new();
+
+	for part in interpolated_string_expression.parts() {
+		match part {
+			StringPart::Text(text) => string.push_str(text),
+			StringPart::Expression(expression) => string.push_str(&evaluate_expression(expression, environment)?.to_string())
+		}
 	}
+
+	Ok(Value::String(string))
 }
 
-fn evaluate_parenthesised_expression(parenthesised_expression: &ParenthesisedExpression) -> f64 {
-	evaluate_expression(parenthesised_expression.content())
+pub fn undefined_variable(identifier_expression: &IdentifierExpression) -> RuntimeError {
+	RuntimeError::new(
+		RuntimeErrorKind::UndefinedVariable {
+			name: identifier_expression.name().to_string()
+		},
+		identifier_expression.span()
+	)
 }
 
-fn evaluate_literal_expression(literal_expression: &LiteralExpression) -> f64 {
-	match literal_expression.kind() {
-		LiteralExpressionKind::Integer |
-		LiteralExpressionKind::FloatingPoint => literal_expression.token().text().parse::<f64>().unwrap(),
+fn evaluate_literal_expression<'a>(literal_expression: &LiteralExpression<'a>) -> Result<Value<'a>, RuntimeError> {
+	let text = literal_expression.token().text();
+
+	Ok(match literal_expression.kind() {
+		LiteralExpressionKind::Integer => match literal_expression.token().value() {
+			Some(TokenValue::Integer(integer)) => Value::Integer(*integer),
+			_ => return Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, literal_expression.span()))
+		},
+		LiteralExpressionKind::FloatingPoint => match literal_expression.token().value() {
+			Some(TokenValue::Float(float)) => Value::Float(*float),
+			_ => return Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, literal_expression.span()))
+		},
+		LiteralExpressionKind::String => match literal_expression.token().value() {
+			Some(TokenValue::String(string)) => Value::String(string.clone()),
+			_ => return Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, literal_expression.span()))
+		},
+		LiteralExpressionKind::Character => match literal_expression.token().value() {
+			Some(TokenValue::Character(character)) => Value::Character(*character),
+			_ => return Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, literal_expression.span()))
+		},
+		LiteralExpressionKind::Boolean => Value::Boolean(text == "true")
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lexing::{
+		Lexer,
+		Token
+	};
+	use crate::parser::Parser;
+	use crate::runtime::Environment;
+	use crate::runtime::evaluation::evaluate_syntax;
+	use crate::diagnostic::{
+		Diagnostic,
+		SourceMap
+	};
+
+	// Runs code without checking its types first, so the evaluator's own errors can be seen.
+	// A value is shown with its type, as in `Integer 3`.
+	fn run(code: &str) -> Result<String, Diagnostic> {
+		let mut source_map = SourceMap::new();
+		let file = source_map.add("<test>", code);
+		let tokens: Vec<Token> = Lexer::new(source_map.file(file)).map(|result| result.unwrap_or_else(|_| panic!("`{}` failed to lex", code))).collect();
+
+		let parser = Parser::new(&tokens);
+		let (syntax, diagnostics) = parser.parse();
+		assert!(diagnostics.is_empty(), "`{}` failed to parse", code);
+
+		match evaluate_syntax(&syntax, &mut Environment::new()) {
+			Ok(value) => Ok(value.map_or(String::new(), |value| format!("{} {}", value.value_type(), value))),
+			Err(error) => Err(error.to_diagnostic())
+		}
+	}
+
+	fn evaluate(code: &str) -> String {
+		run(code).unwrap_or_else(|diagnostic| panic!("`{}` failed with {}", code, diagnostic.code()))
+	}
+
+	// The code of the error the code fails with, and the text its primary label and then its other labels point at.
+	fn fail(code: &str) -> (&'static str, Vec<&str>) {
+		let Err(diagnostic) = run(code) else {
+			panic!("`{}` did not fail", code);
+		};
+
+		let labels = std::iter::once(diagnostic.primary_label()).chain(diagnostic.secondary_labels());
+
+		(diagnostic.code(), labels.map(|label| &code[label.span().start()..label.span().end()]).collect())
+	}
+
+	#[test]
+	fn literals_evaluate_to_values_of_their_type() {
+		assert_eq!(evaluate("42"), "Integer 42");
+		assert_eq!(evaluate("2.5"), "Float 2.5");
+		assert_eq!(evaluate("\"a\""), "String a");
+		assert_eq!(evaluate("'x'"), "Character x");
+		assert_eq!(evaluate("false"), "Boolean false");
+	}
+
+	#[test]
+	fn operands_of_the_wrong_type_are_errors() {
+		assert_eq!(fail("1 + \"a\""), ("E0200", vec!["+", "1", "\"a\""]));
+		assert_eq!(fail("'a' * 2"), ("E0200", vec!["*", "'a'", "2"]));
+		assert_eq!(fail("\"a\" < 1"), ("E0200", vec!["<", "\"a\"", "1"]));
+		assert_eq!(fail("true == 1"), ("E0200", vec!["==", "true", "1"]));
+		assert_eq!(fail("-\"a\""), ("E0201", vec!["-", "\"a\""]));
+		assert_eq!(fail("!1"), ("E0201", vec!["!", "1"]));
+		assert_eq!(fail("if 1 { }"), ("E0205", vec!["1"]));
+		assert_eq!(fail("true && 'a'"), ("E0205", vec!["'a'"]));
+	}
+
+	#[test]
+	fn integer_arithmetic_truncates_and_checks_for_overflow() {
+		assert_eq!(evaluate("7 / 2 == 3"), "Boolean true");
+		assert_eq!(evaluate("-7 / 2"), "Integer -3");
+		assert_eq!(evaluate("7 / -2"), "Integer -3");
+		assert_eq!(evaluate("-7 % 2"), "Integer -1");
+		assert_eq!(evaluate("1 + 2.5"), "Float 3.5");
+		assert_eq!(evaluate("7 / 2.0"), "Float 3.5");
+		assert_eq!(fail("9223372036854775807 + 1"), ("E0203", vec!["+"]));
+		assert_eq!(fail("(-9223372036854775807 - 1) / -1"), ("E0203", vec!["/"]));
+		assert_eq!(fail("-(-9223372036854775807 - 1)"), ("E0203", vec!["-"]));
+		assert_eq!(fail("let x = 5; x % (x - 5)"), ("E0204", vec!["%", "(x - 5)"]));
+		assert_eq!(fail("1 / 0"), ("E0204", vec!["/", "0"]));
+	}
+
+	#[test]
+	fn logical_operators_short_circuit() {
+		assert_eq!(evaluate("false && 1 / 0 == 0"), "Boolean false");
+		assert_eq!(evaluate("true || 1 / 0 == 0"), "Boolean true");
+		assert_eq!(fail("true && 1 / 0 == 0"), ("E0204", vec!["/", "0"]));
+	}
+
+	#[test]
+	fn comparisons_mix_integers_and_floats_and_are_false_with_nan() {
+		assert_eq!(evaluate("1 == 1.0"), "Boolean true");
+		assert_eq!(evaluate("1 != 1.5"), "Boolean true");
+		assert_eq!(evaluate("2 > 1.5 && 'a' < 'b' && \"ab\" < \"b\""), "Boolean true");
+		assert_eq!(evaluate("let nan = 0.0 / 0.0; nan == nan || nan < 1.0 || nan >= nan || 1 > nan"), "Boolean false");
+		assert_eq!(evaluate("let nan = 0.0 / 0.0; nan != nan"), "Boolean true");
+	}
+
+	#[test]
+	fn indices_are_checked_against_the_length() {
+		assert_eq!(evaluate("let a = [1, 2, 3]; a[0] + a[2]"), "Integer 4");
+		assert_eq!(evaluate("let a = [1, 2, 3]; a[1..3]"), "Array [2, 3]");
+		assert_eq!(evaluate("let a = [1, 2, 3]; a[3..3].len() + a[2..1].len()"), "Integer 0");
+		assert_eq!(fail("let a = [1, 2, 3]; a[1 + 2]"), ("E0215", vec!["1 + 2"]));
+		assert_eq!(fail("let a = [1, 2, 3]; a[-1]"), ("E0215", vec!["-1"]));
+		assert_eq!(fail("let a = [1, 2, 3]; a[2..4]"), ("E0216", vec!["2..4"]));
+		assert_eq!(fail("let a = [1, 2, 3]; a[-1..2] = []"), ("E0216", vec!["-1..2"]));
+		assert_eq!(fail("let a = [1]; a[1.5]"), ("E0205", vec!["1.5"]));
+		assert_eq!(fail("let a = 1; a[0]"), ("E0214", vec!["a"]));
+	}
+
+	#[test]
+	fn elements_and_fields_are_changed_in_place() {
+		assert_eq!(evaluate("let a = [1, 2, 3]; a[1..3] = [9]; a"), "Array [1, 9]");
+		assert_eq!(evaluate("let a = [[1], [2, 3]]; a[1][0] = 4; a[0].push(5); a[1][0..1].push(6); a"), "Array [[1, 5], [4, 3]]");
+		assert_eq!(evaluate("struct P { x: Integer, ys: [Integer] } let p = P { x: 1, ys: [] }; p.x = 2; p.ys.push(p.x); p.ys.pop() + p.x"), "Integer 4");
+		assert_eq!(evaluate("let a = [1, 2]; let b = a; b[0] = 3; a[0]"), "Integer 1");
+		assert_eq!(fail("let a = [1, 2]; a[0..1] = 3"), ("E0205", vec!["a[0..1]"]));
+	}
+
+	#[test]
+	fn slices_are_new_arrays() {
+		assert_eq!(evaluate("let a = [1, 2, 3]; a[1..2][0] = 9; a[1..2].push(9); a[0..2].pop() + a[1..3][1] * 10 + a[1..2].len() * 100"), "Integer 132");
+		assert_eq!(evaluate("struct P { xs: [Integer] } let ps = [P { xs: [1] }]; ps[0..1][0].xs.push(2); ps[0..1][0].xs = []; ps[0].xs"), "Array [1]");
+	}
+
+	#[test]
+	fn arrays_grow_and_shrink_at_their_end() {
+		assert_eq!(evaluate("let a = []; a.push(1); a.push(2); a.pop() * 10 + a.len()"), "Integer 21");
+		assert_eq!(evaluate("[1, 2, 3].pop()"), "Integer 3");
+		assert_eq!(fail("let a = [1]; a.pop(); a.pop()"), ("E0217", vec!["a.pop()"]));
+		assert_eq!(fail("[].pop()"), ("E0217", vec!["[].pop()"]));
+		assert_eq!(fail("let a = [1]; a.size()"), ("E0212", vec!["size"]));
+	}
+
+	#[test]
+	fn only_an_if_ending_in_an_else_has_a_value() {
+		assert_eq!(evaluate("if false { 1 } else if true { 2 } else { 3 }"), "Integer 2");
+		assert_eq!(evaluate("if true { 1 }"), "");
+		assert_eq!(evaluate("if false { 1 } else if true { 2 }"), "");
+		assert_eq!(evaluate("fn f() { if true { 1 } } let u = f(); \"${u}\""), "String ()");
+		assert_eq!(evaluate("fn f() { if true { return 1 } 2 } f()"), "Integer 1");
+	}
 
-		_ => panic!("Can only evaluate numbers")
+	#[test]
+	fn for_loops_go_over_the_elements_of_arrays() {
+		assert_eq!(evaluate("let total = 0; for x in [1, 2, 3] { total = total * 10 + x } total"), "Integer 123");
+		assert_eq!(evaluate("let a = [1, 2]; for x in a { a.push(x) } a"), "Array [1, 2, 1, 2]");
+		assert_eq!(fail("for x in 5 { }"), ("E0207", vec!["5"]));
 	}
 }
@@ -1,10 +1,36 @@
 mod expression;
+mod statement;
+mod place;
 
-use expression::evaluate_expression;
-use crate::syntax::Syntax;
+use statement::{
+	Flow,
+	evaluate_statements
+};
+use crate::syntax::{
+	Syntax,
+	Program
+};
+use crate::runtime::{
+	Environment,
+	Value,
+	RuntimeError
+};
 
-pub fn evaluate_syntax(syntax: &Syntax) -> f64 {
+// Evaluates to the value of the program's final expression, unless it is terminated by a `;`.
+pub fn evaluate_syntax<'a>(syntax: &Syntax<'a>, environment: &mut Environment<'a>) -> Result<Option<Value<'a>>, RuntimeError> {
 	match syntax {
-		Syntax::Expression(expression) => evaluate_expression(expression)
+		Syntax::Program(program) => evaluate_program(program, environment)
+	}
+}
+
+// Programs run in the environment's outermost scope, so the REPL keeps their variables.
+fn evaluate_program<'a>(program: &Program<'a>, environment: &mut Environment<'a>) -> Result<Option<Value<'a>>, RuntimeError> {
+	match evaluate_statements(program.statements(), environment)? {
+		Flow::Normal(value) => Ok(value),
+		// The parser rejects `break`, `continue` and `return` outside of loops and functions,
+		// so nothing else reaches the top.
+		Flow::Break |
+		Flow::Continue |
+		Flow::Return(_) => Ok(None)
 	}
 }
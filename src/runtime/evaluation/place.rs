@@ -0,0 +1,213 @@
+use std::ops::Range;
+use crate::syntax::{
+	Expression,
+	IdentifierExpression,
+	FieldAccessExpression,
+	IndexExpression
+};
+use crate::runtime::{
+	Environment,
+	Value,
+	ValueType,
+	RuntimeErrorKind,
+	RuntimeError
+};
+use crate::runtime::evaluation::expression::{
+	evaluate_expression,
+	no_such_field,
+	undefined_variable
+};
+
+// One step from a value to a field or elements of it.
+pub enum Step<'p, 'a> {
+	Field(&'p FieldAccessExpression<'a>),
+	// The index is evaluated when the path is built, so resolving the path has no side effects.
+	Index(&'p IndexExpression<'a>, Value<'a>)
+}
+
+// Leads from a variable through its fields and elements to a value stored in the environment,
+// so it can be read and changed where it is rather than through a copy.
+pub struct PlacePath<'p, 'a> {
+	variable: &'p IdentifierExpression<'a>,
+	steps: Vec<Step<'p, 'a>>
+}
+
+impl<'p, 'a> PlacePath<'p, 'a> {
+	// Evaluates the indices of an expression the parser accepts as an assignment target, from the outside in.
+	pub fn new(expression: &'p Expression<'a>, environment: &mut Environment<'a>) -> Result<Self, RuntimeError> {
+		match expression {
+			Expression::Identifier(identifier_expression) => Ok(Self {
+				variable: identifier_expression,
+				steps: Vec::new()
+			}),
+			Expression::FieldAccess(field_access_expression) => {
+				let mut path = Self::new(field_access_expression.object(), environment)?;
+				path.push(Step::Field(field_access_expression));
+
+				Ok(path)
+			},
+			Expression::Index(index_expression) => {
+				let mut path = Self::new(index_expression.object(), environment)?;
+				let index = evaluate_expression(index_expression.index(), environment)?;
+				path.push(Step::Index(index_expression, index));
+
+				Ok(path)
+			},
+			_ => Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, expression.span()))
+		}
+	}
+
+	pub fn push(&mut self, step: Step<'p, 'a>) {
+		self.steps.push(step);
+	}
+
+	// Follows the path, checking every field and index against the values as they are now.
+	pub fn resolve<'e>(&self, environment: &'e mut Environment<'a>) -> Result<Place<'e, 'a>, RuntimeError> {
+		let value = environment.get_mut(self.variable.name()).ok_or_else(|| undefined_variable(self.variable))?;
+		let mut place = Place::Value(value);
+
+		for step in &self.steps {
+			place = match step {
+				Step::Field(field_access_expression) => place.field(field_access_expression)?,
+				Step::Index(index_expression, index) => place.index(index_expression, index.clone())?
+			};
+		}
+
+		Ok(place)
+	}
+}
+
+// A value stored in the environment, a slice of an array stored there,
+// or a copy of what was reached through a slice, since a slice is a new array.
+pub enum Place<'e, 'a> {
+	Value(&'e mut Value<'a>),
+	Slice(&'e mut Vec<Value<'a>>, Range<usize>),
+	Copy(Value<'a>)
+}
+
+impl<'e, 'a> Place<'e, 'a> {
+	// A copy of what is in the place.
+	pub fn value(&self) -> Value<'a> {
+		match self {
+			Place::Value(value) => (*value).clone(),
+			Place::Slice(elements, range) => Value::Array(elements[range.clone()].to_vec()),
+			Place::Copy(value) => value.clone()
+		}
+	}
+
+	// Assigning to a slice replaces it with the elements of the assigned array, which can be of another length.
+	// Assigning to a copy changes nothing that is stored.
+	pub fn set(self, value: Value<'a>, target: &Expression) -> Result<(), RuntimeError> {
+		match (self, value) {
+			(Place::Value(place), value) => *place = value,
+			(Place::Slice(elements, range), Value::Array(values)) => {
+				elements.splice(range, values);
+			},
+			(Place::Slice(..), value) => return Err(RuntimeError::new(
+				RuntimeErrorKind::UnexpectedType {
+					expected: ValueType::Array,
+					found: value.value_type()
+				},
+				target.span()
+			)),
+			(Place::Copy(_), _) => ()
+		}
+
+		Ok(())
+	}
+
+	pub fn holds_array(&self) -> bool {
+		matches!(self, Place::Value(Value::Array(_)) | Place::Slice(..) | Place::Copy(Value::Array(_)))
+	}
+
+	// The array stored in the place, or the place itself when it holds something else.
+	pub fn elements(self) -> Result<&'e mut Vec<Value<'a>>, Self> {
+		match self {
+			Place::Value(Value::Array(elements)) => Ok(elements),
+			place => Err(place)
+		}
+	}
+
+	pub fn field(self, field_access_expression: &FieldAccessExpression) -> Result<Self, RuntimeError> {
+		let index = match &self {
+			Place::Value(Value::Struct(structure)) |
+			Place::Copy(Value::Struct(structure)) => structure.field_index(field_access_expression.field()),
+			_ => None
+		};
+
+		match (self, index) {
+			(Place::Value(Value::Struct(structure)), Some(index)) => Ok(Place::Value(structure.field_at_mut(index))),
+			(Place::Copy(Value::Struct(mut structure)), Some(index)) => Ok(Place::Copy(structure.field_at_mut(index).clone())),
+			(place, _) => Err(no_such_field(field_access_expression, &place.value()))
+		}
+	}
+
+	fn index(self, index_expression: &IndexExpression, index: Value) -> Result<Self, RuntimeError> {
+		let value = match self {
+			Place::Value(Value::Array(elements)) => return Ok(match position(index_expression, index, elements.len())? {
+				Position::Element(position) => Place::Value(&mut elements[position]),
+				Position::Slice(slice) => Place::Slice(elements, slice)
+			}),
+			Place::Value(value) => value.clone(),
+			Place::Slice(elements, range) => Value::Array(elements[range].to_vec()),
+			Place::Copy(value) => value
+		};
+
+		// Indexing into a slice indexes the new array it is.
+		let mut elements = match value {
+			Value::Array(elements) => elements,
+			value => return Err(RuntimeError::new(
+				RuntimeErrorKind::NotIndexable {
+					found: value.value_type()
+				},
+				index_expression.object().span()
+			))
+		};
+
+		Ok(Place::Copy(match position(index_expression, index, elements.len())? {
+			Position::Element(position) => elements.swap_remove(position),
+			Position::Slice(slice) => Value::Array(elements.drain(slice).collect())
+		}))
+	}
+}
+
+// What an index picks out of an array.
+pub enum Position {
+	Element(usize),
+	Slice(Range<usize>)
+}
+
+// Checks that the index lies within an array of the given length, errors point at the index.
+// A range whose end is not past its start gives an empty slice, as it is an empty range.
+pub fn position(index_expression: &IndexExpression, index: Value, length: usize) -> Result<Position, RuntimeError> {
+	let within = |bound: i64, inclusive: bool| usize::try_from(bound).ok().filter(|&bound| bound < length || (inclusive && bound == length));
+	let span = index_expression.index().span();
+
+	match index {
+		Value::Integer(index) => within(index, false).map(Position::Element).ok_or_else(|| RuntimeError::new(
+			RuntimeErrorKind::IndexOutOfBounds {
+				index,
+				length
+			},
+			span
+		)),
+		Value::Range(start, end) => match (within(start, true), within(end, true)) {
+			(Some(start), Some(end)) => Ok(Position::Slice(start..end.max(start))),
+			_ => Err(RuntimeError::new(
+				RuntimeErrorKind::SliceOutOfBounds {
+					start,
+					end,
+					length
+				},
+				span
+			))
+		},
+		index => Err(RuntimeError::new(
+			RuntimeErrorKind::UnexpectedType {
+				expected: ValueType::Integer,
+				found: index.value_type()
+			},
+			span
+		))
+	}
+}
@@ -0,0 +1,181 @@
+use crate::syntax::{
+	Statement,
+	ExpressionStatement,
+	VariableDeclaration,
+	Block,
+	ElseBranch,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	ReturnStatement
+};
+use crate::runtime::{
+	Environment,
+	Value,
+	Function,
+	RuntimeErrorKind,
+	RuntimeError
+};
+use crate::runtime::evaluation::expression::{
+	evaluate_expression,
+	expect_boolean
+};
+
+// How a statement finished, `break` and `continue` unwind the statements up to the innermost loop
+// and `return` up to the innermost call.
+pub enum Flow<'a> {
+	Normal(Option<Value<'a>>),
+	Break,
+	Continue,
+	Return(Value<'a>)
+}
+
+// Evaluates the statements in order, the value of the last one is the value of them all.
+// Structs and functions are declared up front, so they can be used before their declaration and from one another.
+pub fn evaluate_statements<'a>(statements: &[Statement<'a>], environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	for statement in statements {
+		match statement {
+			Statement::Function(function_declaration) => {
+				environment.define(function_declaration.name(), Value::Function(Function::new(function_declaration.clone())));
+			},
+			Statement::Struct(struct_declaration) => environment.define_struct(struct_declaration.clone()),
+			_ => ()
+		}
+	}
+
+	let mut value = None;
+
+	for statement in statements {
+		match evaluate_statement(statement, environment)? {
+			Flow::Normal(statement_value) => value = statement_value,
+			flow => return Ok(flow)
+		}
+	}
+
+	Ok(Flow::Normal(value))
+}
+
+fn evaluate_statement<'a>(statement: &Statement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	match statement {
+		Statement::Expression(expression_statement) => evaluate_expression_statement(expression_statement, environment),
+		Statement::VariableDeclaration(variable_declaration) => {
+			evaluate_variable_declaration(variable_declaration, environment)?;
+			Ok(Flow::Normal(None))
+		},
+		Statement::Block(block) => evaluate_block(block, environment),
+		Statement::If(if_statement) => evaluate_if_statement(if_statement, environment),
+		Statement::While(while_statement) => evaluate_while_statement(while_statement, environment),
+		Statement::For(for_statement) => evaluate_for_statement(for_statement, environment),
+		Statement::Break => Ok(Flow::Break),
+		Statement::Continue => Ok(Flow::Continue),
+		// Already declared by `evaluate_statements`.
+		Statement::Function(_) |
+		Statement::Struct(_) => Ok(Flow::Normal(None)),
+		Statement::Return(return_statement) => evaluate_return_statement(return_statement, environment)
+	}
+}
+
+fn evaluate_expression_statement<'a>(expression_statement: &ExpressionStatement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	let value = evaluate_expression(expression_statement.expression(), environment)?;
+
+	if expression_statement.terminated() {
+		Ok(Flow::Normal(None))
+	} else {
+		Ok(Flow::Normal(Some(value)))
+	}
+}
+
+fn evaluate_variable_declaration<'a>(variable_declaration: &VariableDeclaration<'a>, environment: &mut Environment<'a>) -> Result<(), RuntimeError> {
+	let value = evaluate_expression(variable_declaration.value(), environment)?;
+	environment.define(variable_declaration.name(), value);
+
+	Ok(())
+}
+
+fn evaluate_block<'a>(block: &Block<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	environment.push_scope();
+	let flow = evaluate_statements(block.statements(), environment);
+	environment.pop_scope();
+
+	flow
+}
+
+// Evaluates to the value of the branch taken, if any.
+// An `if` only has a value when it ends in an `else`, as it has none when no branch is taken.
+fn evaluate_if_statement<'a>(if_statement: &IfStatement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	let flow = if expect_boolean(if_statement.condition(), environment)? {
+		evaluate_block(if_statement.consequence(), environment)?
+	} else {
+		match if_statement.alternative() {
+			Some(ElseBranch::If(else_if_statement)) => evaluate_if_statement(else_if_statement, environment)?,
+			Some(ElseBranch::Block(block)) => evaluate_block(block, environment)?,
+			None => Flow::Normal(None)
+		}
+	};
+
+	Ok(match flow {
+		Flow::Normal(Some(_)) if !ends_in_else(if_statement) => Flow::Normal(None),
+		flow => flow
+	})
+}
+
+fn ends_in_else(if_statement: &IfStatement) -> bool {
+	match if_statement.alternative() {
+		Some(ElseBranch::If(else_if_statement)) => ends_in_else(else_if_statement),
+		Some(ElseBranch::Block(_)) => true,
+		None => false
+	}
+}
+
+fn evaluate_while_statement<'a>(while_statement: &WhileStatement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	while expect_boolean(while_statement.condition(), environment)? {
+		match evaluate_block(while_statement.body(), environment)? {
+			Flow::Break => break,
+			flow @ Flow::Return(_) => return Ok(flow),
+			_ => ()
+		}
+	}
+
+	Ok(Flow::Normal(None))
+}
+
+// Every iteration gets a fresh scope holding the loop variable.
+fn evaluate_for_statement<'a>(for_statement: &ForStatement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	let iterable = evaluate_expression(for_statement.iterable(), environment)?;
+
+	let values: Box<dyn Iterator<Item = Value>> = match iterable {
+		Value::Range(start, end) => Box::new((start..end).map(Value::Integer)),
+		Value::String(string) => Box::new(string.chars().collect::<Vec<_>>().into_iter().map(Value::Character)),
+		Value::Array(elements) => Box::new(elements.into_iter()),
+		value => return Err(RuntimeError::new(
+			RuntimeErrorKind::NotIterable {
+				found: value.value_type()
+			},
+			for_statement.iterable().span()
+		))
+	};
+
+	for value in values {
+		environment.push_scope();
+		environment.define(for_statement.variable(), value);
+		let flow = evaluate_block(for_statement.body(), environment);
+		environment.pop_scope();
+
+		match flow? {
+			Flow::Break => break,
+			flow @ Flow::Return(_) => return Ok(flow),
+			_ => ()
+		}
+	}
+
+	Ok(Flow::Normal(None))
+}
+
+fn evaluate_return_statement<'a>(return_statement: &ReturnStatement<'a>, environment: &mut Environment<'a>) -> Result<Flow<'a>, RuntimeError> {
+	let value = match return_statement.value() {
+		Some(value) => evaluate_expression(value, environment)?,
+		None => Value::Unit
+	};
+
+	Ok(Flow::Return(value))
+}
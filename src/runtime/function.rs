@@ -0,0 +1,26 @@
+use std::rc::Rc;
+use crate::syntax::FunctionDeclaration;
+
+// A function value, two functions are only equal when they come from the same declaration.
+#[derive(Clone)]
+pub struct Function<'a> {
+	declaration: Rc<FunctionDeclaration<'a>>
+}
+
+impl<'a> Function<'a> {
+	pub fn new(declaration: Rc<FunctionDeclaration<'a>>) -> Self {
+		Self {
+			declaration
+		}
+	}
+
+	pub fn declaration(&self) -> &FunctionDeclaration<'a> {
+		&self.declaration
+	}
+}
+
+impl<'a> PartialEq for Function<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		Rc::ptr_eq(&self.declaration, &other.declaration)
+	}
+}
@@ -1,3 +1,18 @@
 mod evaluation;
+mod value;
+mod value_type;
+mod error;
+mod environment;
+mod function;
+mod structure;
 
 pub use evaluation::evaluate_syntax;
+pub use value::Value;
+pub use value_type::ValueType;
+pub use error::{
+	RuntimeErrorKind,
+	RuntimeError
+};
+pub use environment::Environment;
+pub use function::Function;
+pub use structure::Structure;
@@ -0,0 +1,55 @@
+use std::fmt;
+use std::rc::Rc;
+use crate::syntax::StructDeclaration;
+use crate::runtime::Value;
+
+// A struct value, with a value for every field in the order they are declared in.
+// Structs are copied on assignment like every other value.
+#[derive(Clone)]
+pub struct Structure<'a> {
+	declaration: Rc<StructDeclaration<'a>>,
+	fields: Vec<Value<'a>>
+}
+
+impl<'a> Structure<'a> {
+	pub fn new(declaration: Rc<StructDeclaration<'a>>, fields: Vec<Value<'a>>) -> Self {
+		Self {
+			declaration,
+			fields
+		}
+	}
+
+	pub fn declaration(&self) -> &StructDeclaration<'a> {
+		&self.declaration
+	}
+
+	pub fn field(&self, name: &str) -> Option<&Value<'a>> {
+		self.field_index(name).map(|index| &self.fields[index])
+	}
+
+	pub fn field_at_mut(&mut self, index: usize) -> &mut Value<'a> {
+		&mut self.fields[index]
+	}
+
+	pub fn field_index(&self, name: &str) -> Option<usize> {
+		self.declaration.fields().iter().position(|field| field.name() == name)
+	}
+}
+
+// Two structs are only equal when they come from the same declaration.
+impl<'a> PartialEq for Structure<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		Rc::ptr_eq(&self.declaration, &other.declaration) && self.fields == other.fields
+	}
+}
+
+impl<'a> fmt::Display for Structure<'a> {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		let fields = self.declaration.fields().iter()
+			.zip(&self.fields)
+			.map(|(field, value)| format!("{}: {}", field.name(), value))
+			.collect::<Vec<_>>();
+
+		write!(formatter, "{} {{ {} }}", self.declaration.name(), fields.join(", "))
+	}
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+use crate::runtime::{
+	Function,
+	Structure,
+	ValueType
+};
+
+#[derive(Clone, PartialEq)]
+pub enum Value<'a> {
+	Integer(i64),
+	Float(f64),
+	String(String),
+	Character(char),
+	Boolean(bool),
+	// Integers from the start up to, but not including, the end.
+	Range(i64, i64),
+	Function(Function<'a>),
+	Struct(Structure<'a>),
+	Array(Vec<Value<'a>>),
+	// What calling a function that returns nothing evaluates to.
+	Unit
+}
+
+impl<'a> Value<'a> {
+	pub fn value_type(&self) -> ValueType {
+		match self {
+			Value::Integer(_) => ValueType::Integer,
+			Value::Float(_) => ValueType::Float,
+			Value::String(_) => ValueType::String,
+			Value::Character(_) => ValueType::Character,
+			Value::Boolean(_) => ValueType::Boolean,
+			Value::Range(..) => ValueType::Range,
+			Value::Function(_) => ValueType::Function,
+			Value::Struct(_) => ValueType::Struct,
+			Value::Array(_) => ValueType::Array,
+			Value::Unit => ValueType::Unit
+		}
+	}
+}
+
+impl<'a> fmt::Display for Value<'a> {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Value::Integer(integer) => write!(formatter, "{}", integer),
+			// Debug formatting keeps the fractional part, so `2.0` is not shown as an integer.
+			Value::Float(float) => write!(formatter, "{:?}", float),
+			Value::String(string) => write!(formatter, "{}", string),
+			Value::Character(character) => write!(formatter, "{}", character),
+			Value::Boolean(boolean) => write!(formatter, "{}", boolean),
+			Value::Range(start, end) => write!(formatter, "{}..{}", start, end),
+			Value::Function(function) => write!(formatter, "<fn {}>", function.declaration().name()),
+			Value::Struct(structure) => write!(formatter, "{}", structure),
+			Value::Array(elements) => {
+				let elements = elements.iter().map(|element| element.to_string()).collect::<Vec<_>>();
+				write!(formatter, "[{}]", elements.join(", "))
+			},
+			Value::Unit => write!(formatter, "()")
+		}
+	}
+}
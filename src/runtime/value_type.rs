@@ -0,0 +1,33 @@
+use std::fmt;
+
+// What kind of value a runtime error found or expected, named the way the value's type is written.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ValueType {
+	Integer,
+	Float,
+	String,
+	Character,
+	Boolean,
+	Range,
+	Function,
+	Struct,
+	Array,
+	Unit
+}
+
+impl fmt::Display for ValueType {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ValueType::Integer => write!(formatter, "Integer"),
+			ValueType::Float => write!(formatter, "Float"),
+			ValueType::String => write!(formatter, "String"),
+			ValueType::Character => write!(formatter, "Character"),
+			ValueType::Boolean => write!(formatter, "Boolean"),
+			ValueType::Range => write!(formatter, "Range"),
+			ValueType::Function => write!(formatter, "Function"),
+			ValueType::Struct => write!(formatter, "Struct"),
+			ValueType::Array => write!(formatter, "Array"),
+			ValueType::Unit => write!(formatter, "()")
+		}
+	}
+}
@@ -0,0 +1,24 @@
+use crate::syntax::Expression;
+use crate::diagnostic::Span;
+
+pub struct ArrayLiteralExpression<'a> {
+	elements: Vec<Expression<'a>>,
+	span: Span
+}
+
+impl<'a> ArrayLiteralExpression<'a> {
+	pub fn new(elements: Vec<Expression<'a>>, span: Span) -> Self {
+		Self {
+			elements,
+			span
+		}
+	}
+
+	pub fn elements(&self) -> &[Expression<'a>] {
+		&self.elements
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
@@ -0,0 +1,30 @@
+use std::rc::Rc;
+use crate::syntax::Expression;
+use crate::diagnostic::Span;
+
+pub struct AssignmentExpression<'a> {
+	// A variable, or a field of one such as `p.x`.
+	target: Rc<Expression<'a>>,
+	value: Rc<Expression<'a>>
+}
+
+impl<'a> AssignmentExpression<'a> {
+	pub fn new(target: Rc<Expression<'a>>, value: Rc<Expression<'a>>) -> Self {
+		Self {
+			target,
+			value
+		}
+	}
+
+	pub fn target(&self) -> &Expression<'a> {
+		&self.target
+	}
+
+	pub fn value(&self) -> &Expression<'a> {
+		&self.value
+	}
+
+	pub fn span(&self) -> Span {
+		self.target.span().to(self.value.span())
+	}
+}
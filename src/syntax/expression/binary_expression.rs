@@ -2,6 +2,15 @@ use std::rc::Rc;
 use crate::syntax::{
 	Expression
 };
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+	Left,
+	// Chaining these operators without parentheses is an error, as in `a < b < c`.
+	None
+}
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum BinaryExpressionKind {
@@ -9,18 +18,74 @@ pub enum BinaryExpressionKind {
 	Substraction,
 	Multiplication,
 	Division,
-	Modulo
+	Modulo,
+	Equality,
+	NotEquality,
+	LessThan,
+	GreaterThan,
+	LessThanEqualTo,
+	GreaterThanEqualTo,
+	LogicalAnd,
+	LogicalOr,
+	Range
 }
 
 impl BinaryExpressionKind {
 	pub fn precedence(&self) -> u8 {
 		match self {
+			BinaryExpressionKind::Range => 1,
+
+			BinaryExpressionKind::LogicalOr => 2,
+
+			BinaryExpressionKind::LogicalAnd => 3,
+
+			BinaryExpressionKind::Equality |
+			BinaryExpressionKind::NotEquality => 4,
+
+			BinaryExpressionKind::LessThan |
+			BinaryExpressionKind::GreaterThan |
+			BinaryExpressionKind::LessThanEqualTo |
+			BinaryExpressionKind::GreaterThanEqualTo => 5,
+
 			BinaryExpressionKind::Addition |
-			BinaryExpressionKind::Substraction => 1,
+			BinaryExpressionKind::Substraction => 6,
 
 			BinaryExpressionKind::Multiplication |
 			BinaryExpressionKind::Division |
-			BinaryExpressionKind::Modulo => 2
+			BinaryExpressionKind::Modulo => 7
+		}
+	}
+
+	pub fn associativity(&self) -> Associativity {
+		match self {
+			BinaryExpressionKind::Range |
+			BinaryExpressionKind::Equality |
+			BinaryExpressionKind::NotEquality |
+			BinaryExpressionKind::LessThan |
+			BinaryExpressionKind::GreaterThan |
+			BinaryExpressionKind::LessThanEqualTo |
+			BinaryExpressionKind::GreaterThanEqualTo => Associativity::None,
+
+			_ => Associativity::Left
+		}
+	}
+
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			BinaryExpressionKind::Addition => "+",
+			BinaryExpressionKind::Substraction => "-",
+			BinaryExpressionKind::Multiplication => "*",
+			BinaryExpressionKind::Division => "/",
+			BinaryExpressionKind::Modulo => "%",
+			BinaryExpressionKind::Equality => "==",
+			BinaryExpressionKind::NotEquality => "!=",
+			BinaryExpressionKind::LessThan => "<",
+			BinaryExpressionKind::GreaterThan => ">",
+			BinaryExpressionKind::LessThanEqualTo => "<=",
+			BinaryExpressionKind::GreaterThanEqualTo => ">=",
+			BinaryExpressionKind::LogicalAnd => "&&",
+			BinaryExpressionKind::LogicalOr => "||",
+			BinaryExpressionKind::Range => ".."
 		}
 	}
 }
@@ -28,14 +93,16 @@ impl BinaryExpressionKind {
 pub struct BinaryExpression<'a> {
 	left_operand: Rc<Expression<'a>>,
 	right_operand: Rc<Expression<'a>>,
+	operator: &'a Token<'a>,
 	kind: BinaryExpressionKind
 }
 
 impl<'a> BinaryExpression<'a> {
-	pub fn new(left_operand: Rc<Expression<'a>>, right_operand: Rc<Expression<'a>>, kind: BinaryExpressionKind) -> Self {
+	pub fn new(left_operand: Rc<Expression<'a>>, right_operand: Rc<Expression<'a>>, operator: &'a Token<'a>, kind: BinaryExpressionKind) -> Self {
 		Self {
 			left_operand,
 			right_operand,
+			operator,
 			kind
 		}
 	}
@@ -44,19 +111,19 @@ impl<'a> BinaryExpression<'a> {
 		&self.left_operand
 	}
 
-	pub fn left_operand_rc(&self) -> Rc<Expression<'a>> {
-		self.left_operand.clone()
-	}
-
 	pub fn right_operand(&self) -> &Expression<'a> {
 		&self.right_operand
 	}
 
-	pub fn right_operand_rc(&self) -> Rc<Expression<'a>> {
-		self.right_operand.clone()
+	pub fn operator(&self) -> &'a Token<'a> {
+		self.operator
 	}
 
 	pub fn kind(&self) -> BinaryExpressionKind {
 		self.kind
 	}
+
+	pub fn span(&self) -> Span {
+		self.left_operand.span().to(self.right_operand.span())
+	}
 }
@@ -0,0 +1,33 @@
+use std::rc::Rc;
+use crate::syntax::{
+	Expression
+};
+use crate::diagnostic::Span;
+
+pub struct CallExpression<'a> {
+	callee: Rc<Expression<'a>>,
+	arguments: Vec<Expression<'a>>,
+	span: Span
+}
+
+impl<'a> CallExpression<'a> {
+	pub fn new(callee: Rc<Expression<'a>>, arguments: Vec<Expression<'a>>, span: Span) -> Self {
+		Self {
+			callee,
+			arguments,
+			span
+		}
+	}
+
+	pub fn callee(&self) -> &Expression<'a> {
+		&self.callee
+	}
+
+	pub fn arguments(&self) -> &[Expression<'a>] {
+		&self.arguments
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
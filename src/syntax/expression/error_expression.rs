@@ -0,0 +1,18 @@
+use crate::diagnostic::Span;
+
+// Stands in for an expression that could not be parsed, its diagnostic has already been reported.
+pub struct ErrorExpression {
+	span: Span
+}
+
+impl ErrorExpression {
+	pub fn new(span: Span) -> Self {
+		Self {
+			span
+		}
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
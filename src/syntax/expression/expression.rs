@@ -2,12 +2,61 @@ use crate::syntax::{
 	BinaryExpression,
 	UnaryExpression,
 	LiteralExpression,
-	ParenthesisedExpression
+	ParenthesisedExpression,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	InterpolatedStringExpression,
+	ErrorExpression
 };
+use crate::diagnostic::Span;
 
 pub enum Expression<'a> {
 	Binary(BinaryExpression<'a>),
 	Unary(UnaryExpression<'a>),
 	Literal(LiteralExpression<'a>),
-	Parenthesised(ParenthesisedExpression<'a>)
+	Parenthesised(ParenthesisedExpression<'a>),
+	Identifier(IdentifierExpression<'a>),
+	Assignment(AssignmentExpression<'a>),
+	Call(CallExpression<'a>),
+	StructLiteral(StructLiteralExpression<'a>),
+	FieldAccess(FieldAccessExpression<'a>),
+	ArrayLiteral(ArrayLiteralExpression<'a>),
+	Index(IndexExpression<'a>),
+	InterpolatedString(InterpolatedStringExpression<'a>),
+	Error(ErrorExpression)
+}
+
+impl<'a> Expression<'a> {
+	pub fn span(&self) -> Span {
+		match self {
+			Expression::Binary(binary_expression) => binary_expression.span(),
+			Expression::Unary(unary_expression) => unary_expression.span(),
+			Expression::Literal(literal_expression) => literal_expression.span(),
+			Expression::Parenthesised(parenthesised_expression) => parenthesised_expression.span(),
+			Expression::Identifier(identifier_expression) => identifier_expression.span(),
+			Expression::Assignment(assignment_expression) => assignment_expression.span(),
+			Expression::Call(call_expression) => call_expression.span(),
+			Expression::StructLiteral(struct_literal_expression) => struct_literal_expression.span(),
+			Expression::FieldAccess(field_access_expression) => field_access_expression.span(),
+			Expression::ArrayLiteral(array_literal_expression) => array_literal_expression.span(),
+			Expression::Index(index_expression) => index_expression.span(),
+			Expression::InterpolatedString(interpolated_string_expression) => interpolated_string_expression.span(),
+			Expression::Error(error_expression) => error_expression.span()
+		}
+	}
+
+	// A variable, or a field or element of something assignable.
+	pub fn is_assignable(&self) -> bool {
+		match self {
+			Expression::Identifier(_) => true,
+			Expression::FieldAccess(field_access_expression) => field_access_expression.object().is_assignable(),
+			Expression::Index(index_expression) => index_expression.object().is_assignable(),
+			_ => false
+		}
+	}
 }
@@ -0,0 +1,34 @@
+use std::rc::Rc;
+use crate::syntax::Expression;
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct FieldAccessExpression<'a> {
+	object: Rc<Expression<'a>>,
+	field: &'a Token<'a>
+}
+
+impl<'a> FieldAccessExpression<'a> {
+	pub fn new(object: Rc<Expression<'a>>, field: &'a Token<'a>) -> Self {
+		Self {
+			object,
+			field
+		}
+	}
+
+	pub fn object(&self) -> &Expression<'a> {
+		&self.object
+	}
+
+	pub fn field(&self) -> &'a str {
+		self.field.text()
+	}
+
+	pub fn field_span(&self) -> Span {
+		self.field.span()
+	}
+
+	pub fn span(&self) -> Span {
+		self.object.span().to(self.field.span())
+	}
+}
@@ -0,0 +1,22 @@
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct IdentifierExpression<'a> {
+	token: &'a Token<'a>
+}
+
+impl<'a> IdentifierExpression<'a> {
+	pub fn new(token: &'a Token<'a>) -> Self {
+		Self {
+			token
+		}
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.token.text()
+	}
+
+	pub fn span(&self) -> Span {
+		self.token.span()
+	}
+}
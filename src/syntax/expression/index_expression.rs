@@ -0,0 +1,32 @@
+use std::rc::Rc;
+use crate::syntax::Expression;
+use crate::diagnostic::Span;
+
+// `object[index]`, where an index that is a range takes a slice of the array.
+pub struct IndexExpression<'a> {
+	object: Rc<Expression<'a>>,
+	index: Rc<Expression<'a>>,
+	span: Span
+}
+
+impl<'a> IndexExpression<'a> {
+	pub fn new(object: Rc<Expression<'a>>, index: Rc<Expression<'a>>, span: Span) -> Self {
+		Self {
+			object,
+			index,
+			span
+		}
+	}
+
+	pub fn object(&self) -> &Expression<'a> {
+		&self.object
+	}
+
+	pub fn index(&self) -> &Expression<'a> {
+		&self.index
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
@@ -0,0 +1,30 @@
+use crate::syntax::Expression;
+use crate::diagnostic::Span;
+
+pub enum StringPart<'a> {
+	Text(&'a str),
+	Expression(Expression<'a>)
+}
+
+// A string with `${...}` in it, the parts alternate between text and expressions.
+pub struct InterpolatedStringExpression<'a> {
+	parts: Vec<StringPart<'a>>,
+	span: Span
+}
+
+impl<'a> InterpolatedStringExpression<'a> {
+	pub fn new(parts: Vec<StringPart<'a>>, span: Span) -> Self {
+		Self {
+			parts,
+			span
+		}
+	}
+
+	pub fn parts(&self) -> &[StringPart<'a>] {
+		&self.parts
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
@@ -1,11 +1,13 @@
 use crate::lexing::Token;
+use crate::diagnostic::Span;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum LiteralExpressionKind {
 	Integer,
 	FloatingPoint,
 	Character,
-	String
+	String,
+	Boolean
 }
 
 pub struct LiteralExpression<'a> {
@@ -22,10 +24,14 @@ impl<'a> LiteralExpression<'a> {
 	}
 
 	pub fn token(&self) -> &Token<'a> {
-		&self.token
+		self.token
 	}
 
 	pub fn kind(&self) -> LiteralExpressionKind {
 		self.kind
 	}
+
+	pub fn span(&self) -> Span {
+		self.token.span()
+	}
 }
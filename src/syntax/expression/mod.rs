@@ -1,13 +1,24 @@
+#[allow(clippy::module_inception)]
 mod expression;
 mod binary_expression;
 mod unary_expression;
 mod literal_expression;
 mod parenthesised_expression;
+mod identifier_expression;
+mod assignment_expression;
+mod call_expression;
+mod struct_literal_expression;
+mod field_access_expression;
+mod array_literal_expression;
+mod index_expression;
+mod interpolated_string_expression;
+mod error_expression;
 
 pub use expression::{
 	Expression
 };
 pub use binary_expression::{
+	Associativity,
 	BinaryExpressionKind,
 	BinaryExpression
 };
@@ -20,3 +31,18 @@ pub use literal_expression::{
 	LiteralExpression
 };
 pub use parenthesised_expression::ParenthesisedExpression;
+pub use identifier_expression::IdentifierExpression;
+pub use assignment_expression::AssignmentExpression;
+pub use call_expression::CallExpression;
+pub use struct_literal_expression::{
+	FieldInitialiser,
+	StructLiteralExpression
+};
+pub use field_access_expression::FieldAccessExpression;
+pub use array_literal_expression::ArrayLiteralExpression;
+pub use index_expression::IndexExpression;
+pub use interpolated_string_expression::{
+	StringPart,
+	InterpolatedStringExpression
+};
+pub use error_expression::ErrorExpression;
@@ -2,19 +2,26 @@ use std::rc::Rc;
 use crate::syntax::{
 	Expression
 };
+use crate::diagnostic::Span;
 
 pub struct ParenthesisedExpression<'a> {
-	content: Rc<Expression<'a>>
+	content: Rc<Expression<'a>>,
+	span: Span
 }
 
 impl<'a> ParenthesisedExpression<'a> {
-	pub fn new(content: Rc<Expression<'a>>) -> Self {
+	pub fn new(content: Rc<Expression<'a>>, span: Span) -> Self {
 		Self {
-			content
+			content,
+			span
 		}
 	}
 
 	pub fn content(&self) -> &Expression<'a> {
 		&self.content
 	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
 }
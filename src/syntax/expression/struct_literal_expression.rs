@@ -0,0 +1,63 @@
+use crate::syntax::Expression;
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+// A `name: value` pair in a struct literal.
+pub struct FieldInitialiser<'a> {
+	name: &'a Token<'a>,
+	value: Expression<'a>
+}
+
+impl<'a> FieldInitialiser<'a> {
+	pub fn new(name: &'a Token<'a>, value: Expression<'a>) -> Self {
+		Self {
+			name,
+			value
+		}
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn span(&self) -> Span {
+		self.name.span()
+	}
+
+	pub fn value(&self) -> &Expression<'a> {
+		&self.value
+	}
+}
+
+// Such as `Point { x: 1.0, y: 2.0 }`, the fields can be given in any order.
+pub struct StructLiteralExpression<'a> {
+	name: &'a Token<'a>,
+	fields: Vec<FieldInitialiser<'a>>,
+	span: Span
+}
+
+impl<'a> StructLiteralExpression<'a> {
+	pub fn new(name: &'a Token<'a>, fields: Vec<FieldInitialiser<'a>>, span: Span) -> Self {
+		Self {
+			name,
+			fields,
+			span
+		}
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn name_span(&self) -> Span {
+		self.name.span()
+	}
+
+	pub fn fields(&self) -> &[FieldInitialiser<'a>] {
+		&self.fields
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
@@ -2,22 +2,42 @@ use std::rc::Rc;
 use crate::syntax::{
 	Expression
 };
+use crate::lexing::Token;
+use crate::diagnostic::Span;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum UnaryExpressionKind {
 	Identity,
-	Negation
+	Negation,
+	LogicalNot
+}
+
+impl UnaryExpressionKind {
+	// Prefix operators bind tighter than every binary operator, so `-2 + 3` negates only the `2`.
+	pub fn precedence(&self) -> u8 {
+		8
+	}
+
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			UnaryExpressionKind::Identity => "+",
+			UnaryExpressionKind::Negation => "-",
+			UnaryExpressionKind::LogicalNot => "!"
+		}
+	}
 }
 
 pub struct UnaryExpression<'a> {
 	operand: Rc<Expression<'a>>,
+	operator: &'a Token<'a>,
 	kind: UnaryExpressionKind
 }
 
 impl<'a> UnaryExpression<'a> {
-	pub fn new(operand: Rc<Expression<'a>>, kind: UnaryExpressionKind) -> Self {
+	pub fn new(operand: Rc<Expression<'a>>, operator: &'a Token<'a>, kind: UnaryExpressionKind) -> Self {
 		Self {
 			operand,
+			operator,
 			kind
 		}
 	}
@@ -26,7 +46,15 @@ impl<'a> UnaryExpression<'a> {
 		&self.operand
 	}
 
+	pub fn operator(&self) -> &'a Token<'a> {
+		self.operator
+	}
+
 	pub fn kind(&self) -> UnaryExpressionKind {
 		self.kind
 	}
+
+	pub fn span(&self) -> Span {
+		self.operator.span().to(self.operand.span())
+	}
 }
@@ -1,16 +1,48 @@
 mod expression;
+mod statement;
+mod program;
+mod type_annotation;
 
 pub use expression::{
 	Expression,
+	Associativity,
 	BinaryExpressionKind,
 	BinaryExpression,
 	UnaryExpressionKind,
 	UnaryExpression,
 	LiteralExpressionKind,
 	LiteralExpression,
-	ParenthesisedExpression
+	ParenthesisedExpression,
+	IdentifierExpression,
+	AssignmentExpression,
+	CallExpression,
+	FieldInitialiser,
+	StructLiteralExpression,
+	FieldAccessExpression,
+	ArrayLiteralExpression,
+	IndexExpression,
+	StringPart,
+	InterpolatedStringExpression,
+	ErrorExpression
 };
+pub use statement::{
+	Statement,
+	ExpressionStatement,
+	VariableDeclaration,
+	Block,
+	ElseBranch,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	Parameter,
+	FunctionDeclaration,
+	Field,
+	StructDeclaration,
+	ReturnStatement
+};
+pub use program::Program;
+pub use type_annotation::TypeAnnotation;
 
 pub enum Syntax<'a> {
-	Expression(Expression<'a>)
+	Program(Program<'a>)
 }
@@ -0,0 +1,17 @@
+use crate::syntax::Statement;
+
+pub struct Program<'a> {
+	statements: Vec<Statement<'a>>
+}
+
+impl<'a> Program<'a> {
+	pub fn new(statements: Vec<Statement<'a>>) -> Self {
+		Self {
+			statements
+		}
+	}
+
+	pub fn statements(&self) -> &[Statement<'a>] {
+		&self.statements
+	}
+}
@@ -0,0 +1,17 @@
+use crate::syntax::Statement;
+
+pub struct Block<'a> {
+	statements: Vec<Statement<'a>>
+}
+
+impl<'a> Block<'a> {
+	pub fn new(statements: Vec<Statement<'a>>) -> Self {
+		Self {
+			statements
+		}
+	}
+
+	pub fn statements(&self) -> &[Statement<'a>] {
+		&self.statements
+	}
+}
@@ -0,0 +1,24 @@
+use crate::syntax::Expression;
+
+pub struct ExpressionStatement<'a> {
+	expression: Expression<'a>,
+	terminated: bool
+}
+
+impl<'a> ExpressionStatement<'a> {
+	pub fn new(expression: Expression<'a>, terminated: bool) -> Self {
+		Self {
+			expression,
+			terminated
+		}
+	}
+
+	pub fn expression(&self) -> &Expression<'a> {
+		&self.expression
+	}
+
+	// Whether the statement ends with a `;`, which discards its value.
+	pub fn terminated(&self) -> bool {
+		self.terminated
+	}
+}
@@ -0,0 +1,38 @@
+use crate::syntax::{
+	Expression,
+	Block
+};
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct ForStatement<'a> {
+	variable: &'a Token<'a>,
+	iterable: Expression<'a>,
+	body: Block<'a>
+}
+
+impl<'a> ForStatement<'a> {
+	pub fn new(variable: &'a Token<'a>, iterable: Expression<'a>, body: Block<'a>) -> Self {
+		Self {
+			variable,
+			iterable,
+			body
+		}
+	}
+
+	pub fn variable(&self) -> &'a str {
+		self.variable.text()
+	}
+
+	pub fn variable_span(&self) -> Span {
+		self.variable.span()
+	}
+
+	pub fn iterable(&self) -> &Expression<'a> {
+		&self.iterable
+	}
+
+	pub fn body(&self) -> &Block<'a> {
+		&self.body
+	}
+}
@@ -0,0 +1,80 @@
+use crate::syntax::{
+	Block,
+	TypeAnnotation
+};
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct Parameter<'a> {
+	name: &'a Token<'a>,
+	annotation: Option<TypeAnnotation<'a>>
+}
+
+impl<'a> Parameter<'a> {
+	pub fn new(name: &'a Token<'a>, annotation: Option<TypeAnnotation<'a>>) -> Self {
+		Self {
+			name,
+			annotation
+		}
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn span(&self) -> Span {
+		self.name.span()
+	}
+
+	pub fn annotation(&self) -> Option<&TypeAnnotation<'a>> {
+		self.annotation.as_ref()
+	}
+}
+
+pub struct FunctionDeclaration<'a> {
+	documentation: &'a [&'a str],
+	name: &'a Token<'a>,
+	parameters: Vec<Parameter<'a>>,
+	result: Option<TypeAnnotation<'a>>,
+	body: Block<'a>,
+	// From `fn` up to the closing parenthesis of the parameters, or the result type when there is one.
+	signature: Span
+}
+
+impl<'a> FunctionDeclaration<'a> {
+	pub fn new(documentation: &'a [&'a str], name: &'a Token<'a>, parameters: Vec<Parameter<'a>>, result: Option<TypeAnnotation<'a>>, body: Block<'a>, signature: Span) -> Self {
+		Self {
+			documentation,
+			name,
+			parameters,
+			result,
+			body,
+			signature
+		}
+	}
+
+	// The lines of the `///` comments written right before the declaration.
+	pub fn documentation(&self) -> &'a [&'a str] {
+		self.documentation
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn parameters(&self) -> &[Parameter<'a>] {
+		&self.parameters
+	}
+
+	pub fn result(&self) -> Option<&TypeAnnotation<'a>> {
+		self.result.as_ref()
+	}
+
+	pub fn body(&self) -> &Block<'a> {
+		&self.body
+	}
+
+	pub fn signature(&self) -> Span {
+		self.signature
+	}
+}
@@ -0,0 +1,38 @@
+use crate::syntax::{
+	Expression,
+	Block
+};
+
+pub enum ElseBranch<'a> {
+	// `else if`, chained onto the previous `if`.
+	If(Box<IfStatement<'a>>),
+	Block(Block<'a>)
+}
+
+pub struct IfStatement<'a> {
+	condition: Expression<'a>,
+	consequence: Block<'a>,
+	alternative: Option<ElseBranch<'a>>
+}
+
+impl<'a> IfStatement<'a> {
+	pub fn new(condition: Expression<'a>, consequence: Block<'a>, alternative: Option<ElseBranch<'a>>) -> Self {
+		Self {
+			condition,
+			consequence,
+			alternative
+		}
+	}
+
+	pub fn condition(&self) -> &Expression<'a> {
+		&self.condition
+	}
+
+	pub fn consequence(&self) -> &Block<'a> {
+		&self.consequence
+	}
+
+	pub fn alternative(&self) -> Option<&ElseBranch<'a>> {
+		self.alternative.as_ref()
+	}
+}
@@ -0,0 +1,31 @@
+#[allow(clippy::module_inception)]
+mod statement;
+mod expression_statement;
+mod variable_declaration;
+mod block;
+mod if_statement;
+mod while_statement;
+mod for_statement;
+mod function_declaration;
+mod struct_declaration;
+mod return_statement;
+
+pub use statement::Statement;
+pub use expression_statement::ExpressionStatement;
+pub use variable_declaration::VariableDeclaration;
+pub use block::Block;
+pub use if_statement::{
+	ElseBranch,
+	IfStatement
+};
+pub use while_statement::WhileStatement;
+pub use for_statement::ForStatement;
+pub use function_declaration::{
+	Parameter,
+	FunctionDeclaration
+};
+pub use struct_declaration::{
+	Field,
+	StructDeclaration
+};
+pub use return_statement::ReturnStatement;
@@ -0,0 +1,28 @@
+use crate::syntax::Expression;
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct ReturnStatement<'a> {
+	keyword: &'a Token<'a>,
+	value: Option<Expression<'a>>
+}
+
+impl<'a> ReturnStatement<'a> {
+	pub fn new(keyword: &'a Token<'a>, value: Option<Expression<'a>>) -> Self {
+		Self {
+			keyword,
+			value
+		}
+	}
+
+	pub fn value(&self) -> Option<&Expression<'a>> {
+		self.value.as_ref()
+	}
+
+	pub fn span(&self) -> Span {
+		match &self.value {
+			Some(value) => self.keyword.span().to(value.span()),
+			None => self.keyword.span()
+		}
+	}
+}
@@ -0,0 +1,28 @@
+use std::rc::Rc;
+use crate::syntax::{
+	ExpressionStatement,
+	VariableDeclaration,
+	Block,
+	IfStatement,
+	WhileStatement,
+	ForStatement,
+	FunctionDeclaration,
+	StructDeclaration,
+	ReturnStatement
+};
+
+pub enum Statement<'a> {
+	Expression(ExpressionStatement<'a>),
+	VariableDeclaration(VariableDeclaration<'a>),
+	Block(Block<'a>),
+	If(IfStatement<'a>),
+	While(WhileStatement<'a>),
+	For(ForStatement<'a>),
+	Break,
+	Continue,
+	// Shared with the function values created from it.
+	Function(Rc<FunctionDeclaration<'a>>),
+	// Shared with the struct values created from it.
+	Struct(Rc<StructDeclaration<'a>>),
+	Return(ReturnStatement<'a>)
+}
@@ -0,0 +1,64 @@
+use crate::syntax::TypeAnnotation;
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct Field<'a> {
+	name: &'a Token<'a>,
+	annotation: Option<TypeAnnotation<'a>>
+}
+
+impl<'a> Field<'a> {
+	pub fn new(name: &'a Token<'a>, annotation: Option<TypeAnnotation<'a>>) -> Self {
+		Self {
+			name,
+			annotation
+		}
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn span(&self) -> Span {
+		self.name.span()
+	}
+
+	// Only missing when the parser already reported it.
+	pub fn annotation(&self) -> Option<&TypeAnnotation<'a>> {
+		self.annotation.as_ref()
+	}
+}
+
+// Such as `struct Point { x: Float, y: Float }`, the fields keep the order they are declared in.
+pub struct StructDeclaration<'a> {
+	documentation: &'a [&'a str],
+	name: &'a Token<'a>,
+	fields: Vec<Field<'a>>
+}
+
+impl<'a> StructDeclaration<'a> {
+	pub fn new(documentation: &'a [&'a str], name: &'a Token<'a>, fields: Vec<Field<'a>>) -> Self {
+		Self {
+			documentation,
+			name,
+			fields
+		}
+	}
+
+	// The lines of the `///` comments written right before the declaration.
+	pub fn documentation(&self) -> &'a [&'a str] {
+		self.documentation
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn name_span(&self) -> Span {
+		self.name.span()
+	}
+
+	pub fn fields(&self) -> &[Field<'a>] {
+		&self.fields
+	}
+}
@@ -0,0 +1,46 @@
+use std::rc::Rc;
+use crate::syntax::{
+	Expression,
+	TypeAnnotation
+};
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+pub struct VariableDeclaration<'a> {
+	documentation: &'a [&'a str],
+	name: &'a Token<'a>,
+	annotation: Option<TypeAnnotation<'a>>,
+	value: Rc<Expression<'a>>
+}
+
+impl<'a> VariableDeclaration<'a> {
+	pub fn new(documentation: &'a [&'a str], name: &'a Token<'a>, annotation: Option<TypeAnnotation<'a>>, value: Rc<Expression<'a>>) -> Self {
+		Self {
+			documentation,
+			name,
+			annotation,
+			value
+		}
+	}
+
+	// The lines of the `///` comments written right before the declaration.
+	pub fn documentation(&self) -> &'a [&'a str] {
+		self.documentation
+	}
+
+	pub fn name(&self) -> &'a str {
+		self.name.text()
+	}
+
+	pub fn name_span(&self) -> Span {
+		self.name.span()
+	}
+
+	pub fn annotation(&self) -> Option<&TypeAnnotation<'a>> {
+		self.annotation.as_ref()
+	}
+
+	pub fn value(&self) -> &Expression<'a> {
+		&self.value
+	}
+}
@@ -0,0 +1,26 @@
+use crate::syntax::{
+	Expression,
+	Block
+};
+
+pub struct WhileStatement<'a> {
+	condition: Expression<'a>,
+	body: Block<'a>
+}
+
+impl<'a> WhileStatement<'a> {
+	pub fn new(condition: Expression<'a>, body: Block<'a>) -> Self {
+		Self {
+			condition,
+			body
+		}
+	}
+
+	pub fn condition(&self) -> &Expression<'a> {
+		&self.condition
+	}
+
+	pub fn body(&self) -> &Block<'a> {
+		&self.body
+	}
+}
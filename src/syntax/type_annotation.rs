@@ -0,0 +1,31 @@
+use std::fmt;
+use crate::lexing::Token;
+use crate::diagnostic::Span;
+
+// The type written after a `:` or `->`, such as the `Integer` in `let x: Integer = 1`.
+pub enum TypeAnnotation<'a> {
+	Named(&'a Token<'a>),
+	// `[Integer]`, an array of the annotated type.
+	Array {
+		element: Box<TypeAnnotation<'a>>,
+		span: Span
+	}
+}
+
+impl<'a> TypeAnnotation<'a> {
+	pub fn span(&self) -> Span {
+		match self {
+			TypeAnnotation::Named(name) => name.span(),
+			TypeAnnotation::Array { span, .. } => *span
+		}
+	}
+}
+
+impl<'a> fmt::Display for TypeAnnotation<'a> {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TypeAnnotation::Named(name) => write!(formatter, "{}", name.text()),
+			TypeAnnotation::Array { element, .. } => write!(formatter, "[{}]", element)
+		}
+	}
+}
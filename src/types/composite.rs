@@ -1,8 +1,30 @@
-use std::collections::HashMap;
+use std::fmt;
 
 use crate::types::Type;
 
+#[derive(Clone, PartialEq)]
 pub enum CompositeType {
-	UserDefined(HashMap<String, Type>),
-	Array(Box<Type>)
+	// A struct, its fields are kept in the order they are declared in.
+	UserDefined {
+		name: String,
+		fields: Vec<(String, Type)>
+	},
+	Array(Box<Type>),
+	Function {
+		parameters: Vec<Type>,
+		result: Box<Type>
+	}
+}
+
+impl fmt::Display for CompositeType {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CompositeType::UserDefined { name, .. } => write!(formatter, "{}", name),
+			CompositeType::Array(element_type) => write!(formatter, "[{}]", element_type),
+			CompositeType::Function { parameters, result } => {
+				let parameters = parameters.iter().map(|parameter| parameter.to_string()).collect::<Vec<_>>();
+				write!(formatter, "fn({}) -> {}", parameters.join(", "), result)
+			}
+		}
+	}
 }
@@ -1,10 +1,48 @@
 mod composite;
 mod primitive;
+mod scheme;
+
+use std::fmt;
 
 pub use composite::CompositeType;
 pub use primitive::PrimitiveType;
+pub use scheme::TypeScheme;
 
+#[derive(Clone, PartialEq)]
 pub enum Type {
 	Primitive(PrimitiveType),
-	Composite(CompositeType)
+	Composite(CompositeType),
+	// Stands for a type still to be inferred, identified by its index in the type environment.
+	Variable(usize),
+	// The type of something that failed to check, accepted everywhere so a mistake is only reported once.
+	Unknown
+}
+
+impl Type {
+	pub fn is_numeric(&self) -> bool {
+		matches!(self, Type::Primitive(PrimitiveType::Integer | PrimitiveType::Float))
+	}
+
+	pub fn array(element: Type) -> Type {
+		Type::Composite(CompositeType::Array(Box::new(element)))
+	}
+
+	pub fn function(parameters: Vec<Type>, result: Type) -> Type {
+		Type::Composite(CompositeType::Function {
+			parameters,
+			result: Box::new(result)
+		})
+	}
+}
+
+impl fmt::Display for Type {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Type::Primitive(primitive_type) => write!(formatter, "{}", primitive_type),
+			Type::Composite(composite_type) => write!(formatter, "{}", composite_type),
+			// The index of a variable means nothing to the reader, it shows as a type still to be found.
+			Type::Variable(_) |
+			Type::Unknown => write!(formatter, "_")
+		}
+	}
 }
@@ -1,5 +1,41 @@
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PrimitiveType {
 	Integer,
 	Float,
-	String
+	String,
+	Character,
+	Boolean,
+	Range,
+	Unit
+}
+
+impl PrimitiveType {
+	// The primitive type a type annotation names, `()` can not be named.
+	pub fn from_name(name: &str) -> Option<PrimitiveType> {
+		Some(match name {
+			"Integer" => PrimitiveType::Integer,
+			"Float" => PrimitiveType::Float,
+			"String" => PrimitiveType::String,
+			"Character" => PrimitiveType::Character,
+			"Boolean" => PrimitiveType::Boolean,
+			"Range" => PrimitiveType::Range,
+			_ => return None
+		})
+	}
+}
+
+impl fmt::Display for PrimitiveType {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			PrimitiveType::Integer => write!(formatter, "Integer"),
+			PrimitiveType::Float => write!(formatter, "Float"),
+			PrimitiveType::String => write!(formatter, "String"),
+			PrimitiveType::Character => write!(formatter, "Character"),
+			PrimitiveType::Boolean => write!(formatter, "Boolean"),
+			PrimitiveType::Range => write!(formatter, "Range"),
+			PrimitiveType::Unit => write!(formatter, "()")
+		}
+	}
 }
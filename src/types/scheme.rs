@@ -0,0 +1,31 @@
+use crate::types::Type;
+
+// A type that may be generic over some of its type variables, as the type of `fn id(x) { x }` is over the type of `x`.
+// Every use of a generic function gets fresh variables in their place.
+#[derive(Clone)]
+pub struct TypeScheme {
+	variables: Vec<usize>,
+	body: Type
+}
+
+impl TypeScheme {
+	pub fn new(variables: Vec<usize>, body: Type) -> Self {
+		Self {
+			variables,
+			body
+		}
+	}
+
+	// A scheme that is not generic at all, as for variables.
+	pub fn monomorphic(body: Type) -> Self {
+		Self::new(Vec::new(), body)
+	}
+
+	pub fn variables(&self) -> &[usize] {
+		&self.variables
+	}
+
+	pub fn body(&self) -> &Type {
+		&self.body
+	}
+}